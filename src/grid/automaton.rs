@@ -0,0 +1,322 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use crate::{Dir, Vec2D, Vec3D};
+
+use super::Grid;
+
+/// Which neighbors of a cell a rule sees.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Neighborhood {
+    /// Orthogonal neighbors only, one step along a single axis.
+    Cardinals,
+    /// Every neighbor including diagonals.
+    #[default]
+    Compass,
+}
+
+/// Points on an unbounded plane or space that know their neighbors.
+pub trait Neighbors: Copy + Eq + Hash {
+    fn neighbors(&self, neighborhood: Neighborhood) -> Vec<Self>;
+}
+
+impl Neighbors for Vec2D<i64> {
+    fn neighbors(&self, neighborhood: Neighborhood) -> Vec<Self> {
+        match neighborhood {
+            Neighborhood::Cardinals => Dir::<i64>::cardinals_unchecked(self).to_vec(),
+            Neighborhood::Compass => Dir::<i64>::compass_unchecked(self).to_vec(),
+        }
+    }
+}
+
+impl Neighbors for Vec3D<i64> {
+    fn neighbors(&self, neighborhood: Neighborhood) -> Vec<Self> {
+        [self.0, self.1, self.2]
+            .neighbors(neighborhood)
+            .into_iter()
+            .map(|[x, y, z]| Vec3D(x, y, z))
+            .collect()
+    }
+}
+
+impl<const N: usize> Neighbors for [i64; N] {
+    /// Cardinals are the `2 * N` points one step along an axis,
+    /// compass is all `3^N - 1` surrounding points.
+    fn neighbors(&self, neighborhood: Neighborhood) -> Vec<Self> {
+        match neighborhood {
+            Neighborhood::Cardinals => (0..N)
+                .flat_map(|axis| {
+                    [-1, 1].map(|step| {
+                        let mut next = *self;
+                        next[axis] += step;
+                        next
+                    })
+                })
+                .collect(),
+            Neighborhood::Compass => (0..3_usize.pow(N as u32))
+                .filter_map(|mut code| {
+                    let mut next = *self;
+                    for value in next.iter_mut() {
+                        *value += (code % 3) as i64 - 1;
+                        code /= 3;
+                    }
+                    (next != *self).then_some(next)
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A cellular automaton on a dense grid. Each step builds the next generation
+/// into a second buffer from a rule over a cell and its in-bounds neighbors.
+/// ```
+/// use puzlib::{Automaton, FileReader, Neighborhood};
+/// let grid = FileReader::new(".#.\n.#.\n.#.\n").grid();
+/// let mut life = Automaton::new(grid, Neighborhood::Compass, |cell: &char, near: &[&char]| {
+///     match (*cell, near.iter().filter(|c| ***c == '#').count()) {
+///         ('#', 2 | 3) | ('.', 3) => '#',
+///         _ => '.',
+///     }
+/// });
+/// assert_eq!(&FileReader::new("...\n###\n...\n").grid(), life.run(1));
+/// assert_eq!(&FileReader::new(".#.\n.#.\n.#.\n").grid(), life.run(1));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Automaton<T, F> {
+    current: Grid<T>,
+    next: Grid<T>,
+    neighborhood: Neighborhood,
+    rule: F,
+    generation: usize,
+}
+
+impl<T, F> Automaton<T, F>
+where
+    T: Clone + PartialEq,
+    F: Fn(&T, &[&T]) -> T,
+{
+    pub fn new(grid: Grid<T>, neighborhood: Neighborhood, rule: F) -> Self {
+        Self {
+            next: grid.clone(),
+            current: grid,
+            neighborhood,
+            rule,
+            generation: 0,
+        }
+    }
+
+    /// The current generation.
+    pub fn grid(&self) -> &Grid<T> {
+        &self.current
+    }
+
+    pub fn into_grid(self) -> Grid<T> {
+        self.current
+    }
+
+    /// Number of steps taken so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Advance one generation. Returns false if no cell changed.
+    pub fn step(&mut self) -> bool {
+        let mut changed = false;
+        let mut near = Vec::with_capacity(8);
+        for pos in self.current.positions() {
+            near.clear();
+            match self.neighborhood {
+                Neighborhood::Cardinals => {
+                    near.extend(self.current.cardinals(pos).map(|n| &self.current[n]))
+                }
+                Neighborhood::Compass => {
+                    near.extend(self.current.compass(pos).map(|n| &self.current[n]))
+                }
+            }
+            let cell = (self.rule)(&self.current[pos], &near);
+            changed |= cell != self.current[pos];
+            self.next[pos] = cell;
+        }
+        std::mem::swap(&mut self.current, &mut self.next);
+        self.generation += 1;
+        changed
+    }
+
+    /// Advance `steps` generations.
+    pub fn run(&mut self, steps: usize) -> &Grid<T> {
+        for _ in 0..steps {
+            self.step();
+        }
+        &self.current
+    }
+
+    /// Step until a generation matches the one before it.
+    /// Returns the number of steps that changed something.
+    /// Never returns if the automaton cycles or grows forever.
+    pub fn run_until_stable(&mut self) -> usize {
+        let start = self.generation;
+        while self.step() {}
+        self.generation - start - 1
+    }
+}
+
+/// A cellular automaton over an unbounded set of live points in any dimension.
+/// The rule decides from whether a point is alive and how many live neighbors
+/// it has whether it is alive in the next generation.
+/// ```
+/// use puzlib::{Neighborhood, SparseAutomaton};
+/// let live = [[1, 0, 0], [2, 1, 0], [0, 2, 0], [1, 2, 0], [2, 2, 0]];
+/// let mut cubes = SparseAutomaton::new(live, Neighborhood::Compass, |alive, near| {
+///     matches!((alive, near), (true, 2 | 3) | (false, 3))
+/// });
+/// cubes.run(6);
+/// assert_eq!(112, cubes.len());
+/// ```
+#[derive(Debug, Clone)]
+pub struct SparseAutomaton<P, F> {
+    live: HashSet<P>,
+    next: HashSet<P>,
+    counts: HashMap<P, usize>,
+    neighborhood: Neighborhood,
+    rule: F,
+    generation: usize,
+}
+
+impl<P, F> SparseAutomaton<P, F>
+where
+    P: Neighbors,
+    F: Fn(bool, usize) -> bool,
+{
+    pub fn new(live: impl IntoIterator<Item = P>, neighborhood: Neighborhood, rule: F) -> Self {
+        Self {
+            live: live.into_iter().collect(),
+            next: HashSet::new(),
+            counts: HashMap::new(),
+            neighborhood,
+            rule,
+            generation: 0,
+        }
+    }
+
+    /// The live points of the current generation.
+    pub fn live(&self) -> &HashSet<P> {
+        &self.live
+    }
+
+    pub fn into_live(self) -> HashSet<P> {
+        self.live
+    }
+
+    pub fn is_alive(&self, point: &P) -> bool {
+        self.live.contains(point)
+    }
+
+    /// Number of live points.
+    pub fn len(&self) -> usize {
+        self.live.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.live.is_empty()
+    }
+
+    /// Number of steps taken so far.
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Advance one generation. Returns false if the live set did not change.
+    pub fn step(&mut self) -> bool {
+        self.counts.clear();
+        for point in &self.live {
+            self.counts.entry(*point).or_insert(0);
+            for near in point.neighbors(self.neighborhood) {
+                *self.counts.entry(near).or_insert(0) += 1;
+            }
+        }
+        self.next.clear();
+        self.next.extend(
+            self.counts
+                .iter()
+                .filter(|(point, near)| (self.rule)(self.live.contains(point), **near))
+                .map(|(point, _)| *point),
+        );
+        std::mem::swap(&mut self.live, &mut self.next);
+        self.generation += 1;
+        self.live != self.next
+    }
+
+    /// Advance `steps` generations.
+    pub fn run(&mut self, steps: usize) -> &HashSet<P> {
+        for _ in 0..steps {
+            self.step();
+        }
+        &self.live
+    }
+
+    /// Step until a generation matches the one before it.
+    /// Returns the number of steps that changed something.
+    /// Never returns if the automaton cycles or grows forever.
+    pub fn run_until_stable(&mut self) -> usize {
+        let start = self.generation;
+        while self.step() {}
+        self.generation - start - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_neighbor_counts() {
+        assert_eq!(4, Vec2D(0, 0).neighbors(Neighborhood::Cardinals).len());
+        assert_eq!(26, Vec3D(0, 0, 0).neighbors(Neighborhood::Compass).len());
+        assert_eq!(8, [0; 4].neighbors(Neighborhood::Cardinals).len());
+        assert_eq!(80, [0; 4].neighbors(Neighborhood::Compass).len());
+    }
+
+    #[test]
+    fn test_dense_until_stable() {
+        // Seats fill when no neighbor is taken and empty with 4 or more taken neighbors.
+        let grid = Grid::from(vec![vec!['L'; 3]; 3]);
+        let mut seats = Automaton::new(grid, Neighborhood::Compass, |cell, near: &[&char]| {
+            let taken = near.iter().filter(|c| ***c == '#').count();
+            match *cell {
+                'L' if taken == 0 => '#',
+                '#' if taken >= 4 => 'L',
+                c => c,
+            }
+        });
+        assert_eq!(2, seats.run_until_stable());
+        assert_eq!(4, seats.grid().iter().filter(|(_, c)| **c == '#').count());
+    }
+
+    #[test]
+    fn test_sparse_4d() {
+        let live = [
+            [1, 0, 0, 0],
+            [2, 1, 0, 0],
+            [0, 2, 0, 0],
+            [1, 2, 0, 0],
+            [2, 2, 0, 0],
+        ];
+        let mut cubes = SparseAutomaton::new(live, Neighborhood::Compass, |alive, near| {
+            matches!((alive, near), (true, 2 | 3) | (false, 3))
+        });
+        assert_eq!(29, cubes.run(1).len());
+        assert_eq!(1, cubes.generation());
+    }
+
+    #[test]
+    fn test_sparse_stable_block() {
+        let live = [Vec2D(0, 0), Vec2D(0, 1), Vec2D(1, 0), Vec2D(1, 1)];
+        let mut block = SparseAutomaton::new(live, Neighborhood::Compass, |alive, near| {
+            matches!((alive, near), (true, 2 | 3) | (false, 3))
+        });
+        assert_eq!(0, block.run_until_stable());
+        assert_eq!(4, block.len());
+    }
+}
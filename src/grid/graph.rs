@@ -0,0 +1,195 @@
+use std::collections::VecDeque;
+
+use crate::{Graph, ReversibleGraph, Vec2D, Weighted};
+
+use super::Grid;
+
+type Predicate<'a, T> = Box<dyn Fn(Vec2D<usize>, &T) -> bool + 'a>;
+type Weight<'a, T> = Box<dyn Fn(&T, &T) -> usize + 'a>;
+
+/// A [`Graph`] over the cells of a [`Grid`] moving in the cardinal directions.
+/// Build one with [`Grid::graph`].
+/// ```
+/// use puzlib::{FileReader, Vec2D, bfs};
+/// let grid = FileReader::new("S.#\n#..\n#.E\n").grid();
+/// let start = grid.find(&'S').unwrap();
+/// let end = grid.find(&'E').unwrap();
+/// let graph = grid.graph(|c| *c != '#').with_target(end);
+/// assert_eq!(5, bfs(&start, &graph).unwrap().len());
+/// ```
+pub struct GridGraph<'a, T> {
+    grid: &'a Grid<T>,
+    passable: Box<dyn Fn(&T) -> bool + 'a>,
+    goal: Option<Predicate<'a, T>>,
+    weight: Option<Weight<'a, T>>,
+}
+
+impl<T> Grid<T> {
+    /// View the grid as a graph where only cells matching `passable` can be entered.
+    /// No node is a goal and every step costs 1 until set otherwise.
+    pub fn graph<'a>(&'a self, passable: impl Fn(&T) -> bool + 'a) -> GridGraph<'a, T> {
+        GridGraph {
+            grid: self,
+            passable: Box::new(passable),
+            goal: None,
+            weight: None,
+        }
+    }
+}
+
+impl<'a, T> GridGraph<'a, T> {
+    /// Finish the search on cells matching the predicate.
+    pub fn with_goal(mut self, goal: impl Fn(Vec2D<usize>, &T) -> bool + 'a) -> Self {
+        self.goal = Some(Box::new(goal));
+        self
+    }
+
+    /// Finish the search on a single position.
+    pub fn with_target(self, target: Vec2D<usize>) -> Self {
+        self.with_goal(move |pos, _| pos == target)
+    }
+
+    /// Cost of stepping from one cell to the next.
+    pub fn with_weight(mut self, weight: impl Fn(&T, &T) -> usize + 'a) -> Self {
+        self.weight = Some(Box::new(weight));
+        self
+    }
+
+    /// The underlying grid.
+    pub fn grid(&self) -> &'a Grid<T> {
+        self.grid
+    }
+
+    /// Dense counterpart of [`multi_source_bfs`](crate::multi_source_bfs): the number of
+    /// steps to each cell from the closest start, or `None` if it cannot be reached.
+    /// ```
+    /// use puzlib::{FileReader, Vec2D};
+    /// let grid = FileReader::new("S.#\n#..\n").grid();
+    /// let dist = grid.graph(|c| *c != '#').distances([Vec2D(0, 0)]);
+    /// assert_eq!(Some(3), dist[Vec2D(1, 2)]);
+    /// assert_eq!(None, dist[Vec2D(1, 0)]);
+    /// ```
+    pub fn distances(&self, starts: impl IntoIterator<Item = Vec2D<usize>>) -> Grid<Option<usize>> {
+        self.distances_limited(starts, usize::MAX)
+    }
+
+    /// [`GridGraph::distances`] that stops expanding at `max_depth` steps.
+    pub fn distances_limited(
+        &self,
+        starts: impl IntoIterator<Item = Vec2D<usize>>,
+        max_depth: usize,
+    ) -> Grid<Option<usize>> {
+        let mut dist = Grid::new(self.grid.width(), self.grid.height(), None);
+        let mut to_visit = VecDeque::new();
+        for start in starts {
+            if dist[start].is_none() {
+                dist[start] = Some(0);
+                to_visit.push_back((start, 0));
+            }
+        }
+        while let Some((pos, steps)) = to_visit.pop_front() {
+            if steps >= max_depth {
+                continue;
+            }
+            for next in self.moves(&pos) {
+                if dist[next].is_none() {
+                    dist[next] = Some(steps + 1);
+                    to_visit.push_back((next, steps + 1));
+                }
+            }
+        }
+        dist
+    }
+}
+
+impl<T> Graph for GridGraph<'_, T> {
+    type Node = Vec2D<usize>;
+
+    fn height(&self) -> usize {
+        self.grid.height()
+    }
+
+    fn width(&self) -> usize {
+        self.grid.width()
+    }
+
+    fn moves(&self, node: &Self::Node) -> Vec<Self::Node> {
+        self.grid
+            .cardinals(*node)
+            .filter(|n| (self.passable)(&self.grid[*n]))
+            .collect()
+    }
+
+    fn is_done(&self, node: &Self::Node) -> bool {
+        self.goal
+            .as_ref()
+            .is_some_and(|goal| goal(*node, &self.grid[*node]))
+    }
+}
+
+impl<T> ReversibleGraph for GridGraph<'_, T> {
+    /// Every neighbor can step onto a passable cell and nothing can step onto the rest.
+    fn predecessors(&self, node: &Self::Node) -> Vec<Self::Node> {
+        if (self.passable)(&self.grid[*node]) {
+            self.grid.cardinals(*node).collect()
+        } else {
+            Vec::new()
+        }
+    }
+}
+
+impl<T> Weighted for GridGraph<'_, T> {
+    type Cost = usize;
+
+    fn weight(&self, cur: &Self::Node, next: &Self::Node) -> usize {
+        self.weight
+            .as_ref()
+            .map_or(1, |weight| weight(&self.grid[*cur], &self.grid[*next]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FileReader, a_star, bfs, dijkstra};
+
+    use super::*;
+
+    #[test]
+    fn test_dijkstra_weighted_grid() {
+        let grid = FileReader::new("1163\n1381\n2136\n").grid_with(|c| c.to_digit(10).unwrap());
+        let end = Vec2D(2, 3);
+        let graph = grid
+            .graph(|_| true)
+            .with_target(end)
+            .with_weight(|_, next| *next as usize);
+        let (dist, _) = dijkstra(&Vec2D(0, 0), &graph).unwrap();
+        assert_eq!(13, dist[&end]);
+        let (dist, path) = a_star(&Vec2D(0, 0), &graph, |n| n.manhattan(end)).unwrap();
+        assert_eq!(13, dist[&end]);
+        assert_eq!(Some(&end), path.last());
+    }
+
+    #[test]
+    fn test_dense_distances_limited() {
+        let grid = FileReader::new("....\n.#..\n....\n").grid();
+        let graph = grid.graph(|c| *c != '#');
+        let dist = graph.distances_limited([Vec2D(0, 0), Vec2D(2, 3)], 1);
+        let reached = dist.iter().filter(|(_, d)| d.is_some()).count();
+        assert_eq!(6, reached);
+        assert_eq!(None, dist[Vec2D(1, 2)]);
+        let all = graph.distances([Vec2D(0, 0)]);
+        let expected = crate::bfs_distances(&Vec2D(0, 0), &graph);
+        assert!(expected.iter().all(|(pos, d)| all[*pos] == Some(*d)));
+        assert_eq!(11, expected.len());
+    }
+
+    #[test]
+    fn test_goal_predicate_and_walls() {
+        let grid = FileReader::new("S#.\n..#\n#.E\n").grid();
+        let graph = grid.graph(|c| *c != '#').with_goal(|_, c| *c == 'E');
+        let path = bfs(&Vec2D(0, 0), &graph).unwrap();
+        assert_eq!(Some(&Vec2D(2, 2)), path.last());
+        assert_eq!(vec![Vec2D(1, 0)], graph.moves(&Vec2D(0, 0)));
+        assert!(!grid.graph(|_| true).is_done(&Vec2D(2, 2)));
+    }
+}
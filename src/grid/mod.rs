@@ -0,0 +1,261 @@
+use std::ops::{Index, IndexMut};
+
+use crate::{Dir, Vec2D};
+
+mod automaton;
+pub use automaton::{Automaton, Neighborhood, Neighbors, SparseAutomaton};
+
+mod graph;
+pub use graph::GridGraph;
+
+mod regions;
+pub use regions::Region;
+
+mod render;
+pub use render::{Canvas, CanvasDiff, CellDiff};
+
+mod sparse;
+pub use sparse::{Bounded, SparseGrid};
+
+mod tiled;
+pub use tiled::Tiled;
+
+mod transform;
+pub use transform::Transform;
+
+/// A dense rectangular grid stored row by row in a single `Vec`.
+/// Positions are `Vec2D(row, col)` with the origin in the top left.
+/// ```
+/// use puzlib::{Grid, Vec2D};
+/// let grid: Grid<char> = vec![vec!['#', '.'], vec!['.', 'S']].into();
+/// assert_eq!((2, 2), (grid.width(), grid.height()));
+/// assert_eq!(Some(Vec2D(1, 1)), grid.find(&'S'));
+/// assert_eq!('#', grid[Vec2D(0, 0)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Grid<T> {
+    cells: Vec<T>,
+    width: usize,
+    height: usize,
+}
+
+impl<T> Grid<T> {
+    /// A grid with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            cells: vec![fill; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Build a grid from cells listed row by row. Panics if the cells do not fill
+    /// a whole number of rows.
+    pub fn from_cells(cells: Vec<T>, width: usize) -> Self {
+        let height = cells.len().checked_div(width).unwrap_or(0);
+        assert_eq!(
+            width * height,
+            cells.len(),
+            "{} cells do not fill rows of width {width}",
+            cells.len()
+        );
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether the position is inside the grid.
+    pub fn contains(&self, pos: Vec2D<usize>) -> bool {
+        pos.0 < self.height && pos.1 < self.width
+    }
+
+    /// The cell at a position or `None` if out of bounds.
+    pub fn get(&self, pos: Vec2D<usize>) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[pos.0 * self.width + pos.1])
+        } else {
+            None
+        }
+    }
+
+    /// Mutable access to the cell at a position or `None` if out of bounds.
+    pub fn get_mut(&mut self, pos: Vec2D<usize>) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.0 * self.width + pos.1])
+        } else {
+            None
+        }
+    }
+
+    /// A single row of cells.
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    /// All rows, top to bottom.
+    pub fn rows(&self) -> impl DoubleEndedIterator<Item = &[T]> {
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    /// Every position in row order.
+    pub fn positions(&self) -> impl Iterator<Item = Vec2D<usize>> + use<T> {
+        let (width, height) = (self.width, self.height);
+        (0..height).flat_map(move |row| (0..width).map(move |col| Vec2D(row, col)))
+    }
+
+    /// Every position and its cell in row order.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2D<usize>, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Position of the first cell equal to `value`, scanning row by row.
+    pub fn find(&self, value: &T) -> Option<Vec2D<usize>>
+    where
+        T: PartialEq,
+    {
+        self.positions_of(value).next()
+    }
+
+    /// Positions of every cell equal to `value`.
+    pub fn positions_of<'a>(&'a self, value: &'a T) -> impl Iterator<Item = Vec2D<usize>> + 'a
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .filter_map(move |(pos, cell)| (cell == value).then_some(pos))
+    }
+
+    /// A grid of the same shape with every cell converted.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            cells: self.cells.iter().map(f).collect(),
+            width: self.width,
+            height: self.height,
+        }
+    }
+
+    /// In-bounds orthogonal neighbors (N, E, S, W) of a position.
+    pub fn cardinals(&self, pos: Vec2D<usize>) -> impl Iterator<Item = Vec2D<usize>> + '_ {
+        Dir::<usize>::cardinals(&pos)
+            .into_iter()
+            .flatten()
+            .filter(|n| self.contains(*n))
+    }
+
+    /// In-bounds neighbors in all 8 directions (N, NE, E, SE, S, SW, W, NW) of a position.
+    pub fn compass(&self, pos: Vec2D<usize>) -> impl Iterator<Item = Vec2D<usize>> + '_ {
+        Dir::<usize>::compass(&pos)
+            .into_iter()
+            .flatten()
+            .filter(|n| self.contains(*n))
+    }
+}
+
+impl<T> Index<Vec2D<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: Vec2D<usize>) -> &Self::Output {
+        assert!(
+            self.contains(index),
+            "Position {index} is outside a {}x{} grid",
+            self.height,
+            self.width
+        );
+        &self.cells[index.0 * self.width + index.1]
+    }
+}
+
+impl<T> IndexMut<Vec2D<usize>> for Grid<T> {
+    fn index_mut(&mut self, index: Vec2D<usize>) -> &mut Self::Output {
+        assert!(
+            self.contains(index),
+            "Position {index} is outside a {}x{} grid",
+            self.height,
+            self.width
+        );
+        &mut self.cells[index.0 * self.width + index.1]
+    }
+}
+
+impl<T> From<Vec<Vec<T>>> for Grid<T> {
+    /// Convert rows (such as from [`read_grid`](crate::read_grid)) to a grid.
+    /// Panics if the rows have different lengths.
+    fn from(rows: Vec<Vec<T>>) -> Self {
+        let width = rows.first().map(|r| r.len()).unwrap_or(0);
+        let height = rows.len();
+        let mut cells = Vec::with_capacity(width * height);
+        for (idx, row) in rows.into_iter().enumerate() {
+            assert_eq!(width, row.len(), "Row {idx} has a different width");
+            cells.extend(row);
+        }
+        Self {
+            cells,
+            width,
+            height,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Grid<u8> {
+        Grid::from_cells(vec![1, 2, 3, 4, 5, 6], 3)
+    }
+
+    #[test]
+    fn test_get_and_bounds() {
+        let mut grid = sample();
+        assert_eq!(Some(&6), grid.get(Vec2D(1, 2)));
+        assert_eq!(None, grid.get(Vec2D(2, 0)));
+        assert_eq!(None, grid.get(Vec2D(0, 3)));
+        *grid.get_mut(Vec2D(0, 0)).unwrap() = 9;
+        grid[Vec2D(1, 0)] += 1;
+        assert_eq!(
+            vec![&[9, 2, 3][..], &[5, 5, 6][..]],
+            grid.rows().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_positions_of_and_map() {
+        let grid = sample().map(|v| v % 2);
+        let expected = vec![Vec2D(0, 1), Vec2D(1, 0), Vec2D(1, 2)];
+        assert_eq!(expected, grid.positions_of(&0).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_neighbors_in_bounds() {
+        let grid = sample();
+        let expected = vec![Vec2D(0, 1), Vec2D(1, 0)];
+        assert_eq!(expected, grid.cardinals(Vec2D(0, 0)).collect::<Vec<_>>());
+        let expected = vec![
+            Vec2D(0, 1),
+            Vec2D(0, 2),
+            Vec2D(1, 2),
+            Vec2D(1, 0),
+            Vec2D(0, 0),
+        ];
+        assert_eq!(expected, grid.compass(Vec2D(1, 1)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    #[should_panic(expected = "outside a 2x3 grid")]
+    fn test_index_out_of_bounds() {
+        let _ = sample()[Vec2D(2, 0)];
+    }
+}
@@ -0,0 +1,182 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{Dir, DisjointSet, Vec2D};
+
+use super::Grid;
+
+/// A connected set of grid cells.
+/// ```
+/// use puzlib::{FileReader, Vec2D};
+/// let grid = FileReader::new("AAAA\nBBCD\nBBCC\nEEEC\n").grid();
+/// let plot = grid.flood_fill(Vec2D(1, 2), |a, b| a == b);
+/// assert_eq!((4, 10, 8), (plot.area(), plot.perimeter(), plot.sides()));
+/// assert_eq!((Vec2D(1, 2), Vec2D(3, 3)), plot.bounds());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    cells: HashSet<Vec2D<usize>>,
+    min: Vec2D<usize>,
+    max: Vec2D<usize>,
+}
+
+impl Region {
+    fn new(cells: HashSet<Vec2D<usize>>) -> Self {
+        let mut iter = cells.iter();
+        let first = *iter.next().expect("A region has at least one cell");
+        let (min, max) = iter.fold((first, first), |(min, max), p| {
+            (
+                Vec2D(min.0.min(p.0), min.1.min(p.1)),
+                Vec2D(max.0.max(p.0), max.1.max(p.1)),
+            )
+        });
+        Self { cells, min, max }
+    }
+
+    pub fn cells(&self) -> &HashSet<Vec2D<usize>> {
+        &self.cells
+    }
+
+    pub fn into_cells(self) -> HashSet<Vec2D<usize>> {
+        self.cells
+    }
+
+    pub fn contains(&self, pos: &Vec2D<usize>) -> bool {
+        self.cells.contains(pos)
+    }
+
+    /// Top left and bottom right corners of the bounding box, inclusive.
+    pub fn bounds(&self) -> (Vec2D<usize>, Vec2D<usize>) {
+        (self.min, self.max)
+    }
+
+    /// Number of cells.
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    fn has(&self, pos: Vec2D<i64>) -> bool {
+        usize::try_from(pos.0)
+            .ok()
+            .zip(usize::try_from(pos.1).ok())
+            .is_some_and(|(row, col)| self.cells.contains(&Vec2D(row, col)))
+    }
+
+    fn signed_cells(&self) -> impl Iterator<Item = Vec2D<i64>> + '_ {
+        self.cells.iter().map(|p| Vec2D(p.0 as i64, p.1 as i64))
+    }
+
+    /// Number of cell edges that border a cell outside the region.
+    pub fn perimeter(&self) -> usize {
+        self.signed_cells()
+            .flat_map(|p| Dir::<i64>::cardinals_unchecked(&p))
+            .filter(|n| !self.has(*n))
+            .count()
+    }
+
+    /// Number of straight fence sides around the region, including around holes.
+    /// Equal to the number of corners.
+    pub fn sides(&self) -> usize {
+        self.signed_cells()
+            .map(|p| {
+                let [n, ne, e, se, s, sw, w, nw] =
+                    Dir::<i64>::compass_unchecked(&p).map(|n| self.has(n));
+                [(n, e, ne), (e, s, se), (s, w, sw), (w, n, nw)]
+                    .into_iter()
+                    .filter(|(a, b, diagonal)| (!a && !b) || (*a && *b && !diagonal))
+                    .count()
+            })
+            .sum()
+    }
+}
+
+impl<T> Grid<T> {
+    /// The region of cells reachable from `start` through orthogonal steps
+    /// between cells where `same(current, next)` holds.
+    pub fn flood_fill(&self, start: Vec2D<usize>, same: impl Fn(&T, &T) -> bool) -> Region {
+        let mut cells = HashSet::from([start]);
+        let mut stack = vec![start];
+        while let Some(pos) = stack.pop() {
+            for next in self.cardinals(pos) {
+                if !cells.contains(&next) && same(&self[pos], &self[next]) {
+                    cells.insert(next);
+                    stack.push(next);
+                }
+            }
+        }
+        Region::new(cells)
+    }
+
+    /// Split every cell of the grid into connected regions where orthogonal
+    /// neighbors belong together if `same` holds for them.
+    /// Regions are ordered by their first cell scanning row by row.
+    /// ```
+    /// use puzlib::FileReader;
+    /// let grid = FileReader::new("OOOOO\nOXOXO\nOOOOO\n").grid();
+    /// let regions = grid.regions(|a, b| a == b);
+    /// assert_eq!(3, regions.len());
+    /// assert_eq!(vec![13, 1, 1], regions.iter().map(|r| r.area()).collect::<Vec<_>>());
+    /// ```
+    pub fn regions(&self, same: impl Fn(&T, &T) -> bool) -> Vec<Region> {
+        let index = |p: Vec2D<usize>| p.0 * self.width + p.1;
+        let mut sets = DisjointSet::init_size(self.cells.len());
+        for pos in self.positions() {
+            for next in [Vec2D(pos.0, pos.1 + 1), Vec2D(pos.0 + 1, pos.1)] {
+                if self.contains(next) && same(&self[pos], &self[next]) {
+                    sets.union(index(pos), index(next));
+                }
+            }
+        }
+        let mut order = Vec::new();
+        let mut groups: HashMap<usize, HashSet<Vec2D<usize>>> = HashMap::new();
+        for pos in self.positions() {
+            let root = sets.find_root(index(pos));
+            groups
+                .entry(root)
+                .or_insert_with(|| {
+                    order.push(root);
+                    HashSet::new()
+                })
+                .insert(pos);
+        }
+        order
+            .into_iter()
+            .map(|root| Region::new(groups.remove(&root).unwrap()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::FileReader;
+
+    use super::*;
+
+    #[test]
+    fn test_regions_price() {
+        let grid = FileReader::new("RRRRIICCFF\nRRRRIICCCF\nVVRRRCCFFF\nVVRCCCJFFF\nVVVVCJJCFE\nVVIVCCJJEE\nVVIIICJJEE\nMIIIIIJJEE\nMIIISIJEEE\nMMMISSJEEE\n").grid();
+        let regions = grid.regions(|a, b| a == b);
+        assert_eq!(11, regions.len());
+        let price = regions
+            .iter()
+            .map(|r| r.area() * r.perimeter())
+            .sum::<usize>();
+        let bulk = regions.iter().map(|r| r.area() * r.sides()).sum::<usize>();
+        assert_eq!((1930, 1206), (price, bulk));
+    }
+
+    #[test]
+    fn test_sides_with_holes() {
+        let grid = FileReader::new("AAAAAA\nAAABBA\nAAABBA\nABBAAA\nABBAAA\nAAAAAA\n").grid();
+        let outer = grid.flood_fill(Vec2D(0, 0), |a, b| a == b);
+        assert_eq!(28, outer.area());
+        assert_eq!(12, outer.sides());
+    }
+
+    #[test]
+    fn test_flood_fill_predicate() {
+        let grid = Grid::from(vec![vec![1, 2, 9], vec![9, 3, 4]]);
+        let basin = grid.flood_fill(Vec2D(0, 0), |_, next| *next != 9);
+        assert_eq!(4, basin.area());
+        assert!(!basin.contains(&Vec2D(1, 0)));
+    }
+}
@@ -0,0 +1,305 @@
+use std::fmt::Display;
+
+use crate::Vec2D;
+
+use super::{Bounded, Grid, SparseGrid};
+
+/// Characters laid out on a plane for printing grids, sparse maps and point sets.
+/// Positions are `Vec2D(row, col)`; the canvas covers only the area it was built from.
+/// ```
+/// use puzlib::{Canvas, FileReader, Vec2D};
+/// let grid = FileReader::new("S..\n.#.\n..E\n").grid();
+/// let path = [Vec2D(0, 1), Vec2D(0, 2), Vec2D(1, 2)];
+/// let canvas = Canvas::from_grid(&grid, |c| *c).overlay(path, 'o');
+/// assert_eq!("Soo\n.#o\n..E\n", canvas.to_string());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Canvas {
+    rows: Vec<Vec<char>>,
+    origin: Vec2D<i64>,
+}
+
+/// A position where two canvases differ. `None` means the position is outside that canvas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CellDiff {
+    pub pos: Vec2D<i64>,
+    pub left: Option<char>,
+    pub right: Option<char>,
+}
+
+/// The cell by cell differences between two canvases.
+/// Displays both canvases side by side with a third panel marking differences with `^`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanvasDiff {
+    pub cells: Vec<CellDiff>,
+    left: Canvas,
+    right: Canvas,
+}
+
+fn to_i64<T: TryInto<i64>>(pos: Vec2D<T>) -> Vec2D<i64> {
+    let convert = |v: T| {
+        v.try_into()
+            .ok()
+            .expect("Coordinate does not fit in an i64")
+    };
+    Vec2D(convert(pos.0), convert(pos.1))
+}
+
+impl Canvas {
+    /// A canvas of `height` rows and `width` columns filled with `fill`, with its top left at `origin`.
+    pub fn new(origin: Vec2D<i64>, width: usize, height: usize, fill: char) -> Self {
+        Self {
+            rows: vec![vec![fill; width]; height],
+            origin,
+        }
+    }
+
+    /// Draw every cell of a grid.
+    pub fn from_grid<T>(grid: &Grid<T>, glyph: impl Fn(&T) -> char) -> Self {
+        Self {
+            rows: grid
+                .rows()
+                .map(|row| row.iter().map(&glyph).collect())
+                .collect(),
+            origin: Vec2D(0, 0),
+        }
+    }
+
+    /// Draw rows of cells such as from [`read_grid`](crate::read_grid).
+    pub fn from_rows<T>(rows: &[Vec<T>], glyph: impl Fn(&T) -> char) -> Self {
+        Self {
+            rows: rows
+                .iter()
+                .map(|row| row.iter().map(&glyph).collect())
+                .collect(),
+            origin: Vec2D(0, 0),
+        }
+    }
+
+    /// Draw the bounding box of a sparse grid using `empty` for unoccupied points.
+    pub fn from_sparse<T, V>(
+        grid: &SparseGrid<Vec2D<T>, V>,
+        empty: char,
+        glyph: impl Fn(&V) -> char,
+    ) -> Self
+    where
+        Vec2D<T>: Bounded,
+        T: Copy + TryInto<i64>,
+    {
+        let mut canvas = Self::bounding(grid.keys().map(|p| to_i64(*p)), empty);
+        for (pos, value) in grid.iter() {
+            canvas.set(to_i64(*pos), glyph(value));
+        }
+        canvas
+    }
+
+    /// Draw the bounding box of a set of points, using `on` for the points and `off` elsewhere.
+    /// ```
+    /// use puzlib::{Canvas, Vec2D};
+    /// let canvas = Canvas::from_points([Vec2D(-1, 0), Vec2D(0, 2)], '#', '.');
+    /// assert_eq!("#..\n..#\n", canvas.to_string());
+    /// ```
+    pub fn from_points<T: Copy + TryInto<i64>>(
+        points: impl IntoIterator<Item = Vec2D<T>>,
+        on: char,
+        off: char,
+    ) -> Self {
+        let points = points.into_iter().map(to_i64).collect::<Vec<_>>();
+        let mut canvas = Self::bounding(points.iter().copied(), off);
+        for pos in points {
+            canvas.set(pos, on);
+        }
+        canvas
+    }
+
+    fn bounding(points: impl Iterator<Item = Vec2D<i64>>, fill: char) -> Self {
+        let bounds = points.fold(None, |bounds: Option<(Vec2D<i64>, Vec2D<i64>)>, p| {
+            Some(match bounds {
+                Some((min, max)) => (min.min_each(&p), max.max_each(&p)),
+                None => (p, p),
+            })
+        });
+        match bounds {
+            Some((min, max)) => Self::new(
+                min,
+                (max.1 - min.1 + 1) as usize,
+                (max.0 - min.0 + 1) as usize,
+                fill,
+            ),
+            None => Self::new(Vec2D(0, 0), 0, 0, fill),
+        }
+    }
+
+    /// Draw a mark over each point, such as a path returned by a search.
+    /// Points outside the canvas are ignored.
+    pub fn overlay<T: Copy + TryInto<i64>>(
+        mut self,
+        points: impl IntoIterator<Item = Vec2D<T>>,
+        mark: char,
+    ) -> Self {
+        for pos in points {
+            self.set(to_i64(pos), mark);
+        }
+        self
+    }
+
+    /// The character at a position or `None` if it is outside the canvas.
+    pub fn get(&self, pos: Vec2D<i64>) -> Option<char> {
+        let Vec2D(row, col) = pos - self.origin;
+        let row = usize::try_from(row).ok()?;
+        let col = usize::try_from(col).ok()?;
+        self.rows.get(row)?.get(col).copied()
+    }
+
+    /// Change the character at a position. Returns false if it is outside the canvas.
+    pub fn set(&mut self, pos: Vec2D<i64>, glyph: char) -> bool {
+        let Vec2D(row, col) = pos - self.origin;
+        let cell = usize::try_from(row)
+            .ok()
+            .zip(usize::try_from(col).ok())
+            .and_then(|(row, col)| self.rows.get_mut(row)?.get_mut(col));
+        match cell {
+            Some(cell) => {
+                *cell = glyph;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Top left and bottom right positions covered by either canvas.
+    fn extent(&self, other: &Canvas) -> (Vec2D<i64>, Vec2D<i64>) {
+        let end = |c: &Canvas| {
+            let width = c.rows.iter().map(|r| r.len()).max().unwrap_or(0) as i64;
+            c.origin + Vec2D(c.rows.len() as i64, width)
+        };
+        (
+            self.origin.min_each(&other.origin),
+            end(self).max_each(&end(other)),
+        )
+    }
+
+    /// Compare with another canvas position by position.
+    /// ```
+    /// use puzlib::{Canvas, Grid};
+    /// let expected = Grid::from_cells(vec!['.', '#', '.', '.'], 2);
+    /// let actual = Grid::from_cells(vec!['.', '#', '#', '.'], 2);
+    /// let diff = Canvas::from_grid(&expected, |c| *c).diff(&Canvas::from_grid(&actual, |c| *c));
+    /// assert_eq!(1, diff.cells.len());
+    /// assert_eq!("1 cells differ\n.#  .#    \n..  #.  ^ \n", diff.to_string());
+    /// ```
+    pub fn diff(&self, other: &Canvas) -> CanvasDiff {
+        let (min, max) = self.extent(other);
+        let cells = (min.0..max.0)
+            .flat_map(|row| (min.1..max.1).map(move |col| Vec2D(row, col)))
+            .filter_map(|pos| {
+                let (left, right) = (self.get(pos), other.get(pos));
+                (left != right).then_some(CellDiff { pos, left, right })
+            })
+            .collect();
+        CanvasDiff {
+            cells,
+            left: self.clone(),
+            right: other.clone(),
+        }
+    }
+}
+
+impl Display for Canvas {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in &self.rows {
+            writeln!(f, "{}", row.iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
+
+impl CanvasDiff {
+    /// Whether the canvases match.
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+}
+
+impl Display for CanvasDiff {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} cells differ", self.cells.len())?;
+        let (min, max) = self.left.extent(&self.right);
+        for row in min.0..max.0 {
+            let panel = |cell: &dyn Fn(Vec2D<i64>) -> char| {
+                (min.1..max.1)
+                    .map(|col| cell(Vec2D(row, col)))
+                    .collect::<String>()
+            };
+            let left = panel(&|pos| self.left.get(pos).unwrap_or(' '));
+            let right = panel(&|pos| self.right.get(pos).unwrap_or(' '));
+            let marks = panel(&|pos| {
+                if self.left.get(pos) == self.right.get(pos) {
+                    ' '
+                } else {
+                    '^'
+                }
+            });
+            writeln!(f, "{left}  {right}  {marks}")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparse_canvas() {
+        let grid: SparseGrid<Vec2D<i64>, u8> =
+            [(Vec2D(-2, 3), 1), (Vec2D(0, 5), 2)].into_iter().collect();
+        let canvas = Canvas::from_sparse(&grid, ' ', |v| (b'0' + v) as char);
+        assert_eq!("1  \n   \n  2\n", canvas.to_string());
+        assert_eq!(Some('1'), canvas.get(Vec2D(-2, 3)));
+        assert_eq!(None, canvas.get(Vec2D(0, 0)));
+    }
+
+    #[test]
+    fn test_overlay_ignores_outside() {
+        let rows = vec![vec![0, 1], vec![1, 0]];
+        let canvas = Canvas::from_rows(&rows, |v| if *v == 1 { '#' } else { '.' })
+            .overlay([Vec2D(0_usize, 0), Vec2D(5, 5)], '@');
+        assert_eq!("@#\n#.\n", canvas.to_string());
+    }
+
+    #[test]
+    fn test_diff_different_shapes() {
+        let left = Canvas::from_points([Vec2D(0, 0)], '#', '.');
+        let right = Canvas::from_points([Vec2D(0, 0), Vec2D(0, 1)], '#', '.');
+        let diff = left.diff(&right);
+        assert_eq!(
+            vec![CellDiff {
+                pos: Vec2D(0, 1),
+                left: None,
+                right: Some('#')
+            }],
+            diff.cells
+        );
+        assert!(left.diff(&left).is_empty());
+    }
+
+    #[test]
+    fn test_diff_ragged_rows() {
+        let left = Canvas::from_rows(&[vec!['#'], vec!['#', '#', '#']], |c| *c);
+        let right = Canvas::from_rows(&[vec!['#'], vec!['#', '#']], |c| *c);
+        let diff = left.diff(&right);
+        assert_eq!(
+            vec![CellDiff {
+                pos: Vec2D(1, 2),
+                left: Some('#'),
+                right: None
+            }],
+            diff.cells
+        );
+        assert_eq!(
+            "1 cells differ\n#    #       \n###  ##     ^\n",
+            diff.to_string()
+        );
+    }
+}
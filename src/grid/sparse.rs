@@ -0,0 +1,299 @@
+use std::{collections::HashMap, fmt::Debug, hash::Hash};
+
+use crate::{CheckedAdd, CheckedSub, Dir, Vec2D, Vec3D};
+
+use super::{Neighborhood, Neighbors};
+
+/// Points whose bounding box can be tracked one component at a time.
+pub trait Bounded: Copy + Eq + Hash {
+    /// The smallest value of each component.
+    fn min_each(&self, other: &Self) -> Self;
+    /// The largest value of each component.
+    fn max_each(&self, other: &Self) -> Self;
+    /// Whether any component lies on the edge of the box from `min` to `max`.
+    fn on_edge(&self, min: &Self, max: &Self) -> bool;
+}
+
+impl<T: Copy + Ord + Hash> Bounded for Vec2D<T> {
+    fn min_each(&self, other: &Self) -> Self {
+        Vec2D(self.0.min(other.0), self.1.min(other.1))
+    }
+
+    fn max_each(&self, other: &Self) -> Self {
+        Vec2D(self.0.max(other.0), self.1.max(other.1))
+    }
+
+    fn on_edge(&self, min: &Self, max: &Self) -> bool {
+        self.0 == min.0 || self.1 == min.1 || self.0 == max.0 || self.1 == max.1
+    }
+}
+
+impl<T: Copy + Ord + Hash> Bounded for Vec3D<T> {
+    fn min_each(&self, other: &Self) -> Self {
+        Vec3D(
+            self.0.min(other.0),
+            self.1.min(other.1),
+            self.2.min(other.2),
+        )
+    }
+
+    fn max_each(&self, other: &Self) -> Self {
+        Vec3D(
+            self.0.max(other.0),
+            self.1.max(other.1),
+            self.2.max(other.2),
+        )
+    }
+
+    fn on_edge(&self, min: &Self, max: &Self) -> bool {
+        self.0 == min.0
+            || self.1 == min.1
+            || self.2 == min.2
+            || self.0 == max.0
+            || self.1 == max.1
+            || self.2 == max.2
+    }
+}
+
+/// Values stored only at occupied points, keeping track of the bounding box.
+/// ```
+/// use puzlib::{FileReader, SparseGrid, Vec2D};
+/// let reader = FileReader::new("..#\n#..\n");
+/// let rocks: SparseGrid<Vec2D<usize>, char> = reader.read_grid(&['.'], |p| p.into()).collect();
+/// assert_eq!(Some((Vec2D(0, 0), Vec2D(1, 2))), rocks.bounds());
+/// assert_eq!("..#\n#..\n", rocks.render('.', |c| *c));
+/// ```
+#[derive(Debug, Clone)]
+pub struct SparseGrid<K, V> {
+    cells: HashMap<K, V>,
+    bounds: Option<(K, K)>,
+}
+
+impl<K: Bounded, V> Default for SparseGrid<K, V> {
+    fn default() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<K: Bounded, V> SparseGrid<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the value at a point, returning the previous value.
+    pub fn insert(&mut self, key: K, value: V) -> Option<V> {
+        self.bounds = Some(match self.bounds {
+            Some((min, max)) => (min.min_each(&key), max.max_each(&key)),
+            None => (key, key),
+        });
+        self.cells.insert(key, value)
+    }
+
+    /// Clear a point, returning its value. The bounds shrink if the point was on an edge.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let value = self.cells.remove(key)?;
+        if let Some((min, max)) = self.bounds
+            && key.on_edge(&min, &max)
+        {
+            self.bounds = self.cells.keys().fold(None, |bounds, k| {
+                Some(match bounds {
+                    Some((min, max)) => (k.min_each(&min), k.max_each(&max)),
+                    None => (*k, *k),
+                })
+            });
+        }
+        Some(value)
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cells.get(key)
+    }
+
+    /// Mutable access to an occupied point.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.cells.get_mut(key)
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.cells.contains_key(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// Smallest and largest value of each component over the occupied points.
+    pub fn bounds(&self) -> Option<(K, K)> {
+        self.bounds
+    }
+
+    /// Occupied points and their values in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.cells.iter()
+    }
+
+    /// Occupied points in no particular order.
+    pub fn keys(&self) -> impl Iterator<Item = &K> {
+        self.cells.keys()
+    }
+}
+
+impl<T, V> SparseGrid<Vec2D<T>, V>
+where
+    T: Debug + Copy + Ord + Hash + From<u8> + CheckedAdd + CheckedSub,
+{
+    /// Occupied orthogonal neighbors (N, E, S, W) of a point.
+    pub fn cardinal_neighbors(&self, pos: &Vec2D<T>) -> impl Iterator<Item = (Vec2D<T>, &V)> {
+        Dir::cardinals(pos)
+            .into_iter()
+            .flatten()
+            .filter_map(|n| self.get(&n).map(|v| (n, v)))
+    }
+
+    /// Occupied neighbors in all 8 directions (N, NE, E, SE, S, SW, W, NW) of a point.
+    pub fn compass_neighbors(&self, pos: &Vec2D<T>) -> impl Iterator<Item = (Vec2D<T>, &V)> {
+        Dir::compass(pos)
+            .into_iter()
+            .flatten()
+            .filter_map(|n| self.get(&n).map(|v| (n, v)))
+    }
+
+    /// Draw the bounding box row by row, using `empty` for unoccupied points.
+    /// Each row ends with a new line.
+    pub fn render(&self, empty: char, glyph: impl Fn(&V) -> char) -> String {
+        let Some((min, max)) = self.bounds else {
+            return String::new();
+        };
+        let one = T::from(1);
+        let mut out = String::new();
+        let mut row = min.0;
+        loop {
+            let mut col = min.1;
+            loop {
+                out.push(self.get(&Vec2D(row, col)).map_or(empty, &glyph));
+                if col == max.1 {
+                    break;
+                }
+                col = col.checked_add(&one).unwrap();
+            }
+            out.push('\n');
+            if row == max.0 {
+                break;
+            }
+            row = row.checked_add(&one).unwrap();
+        }
+        out
+    }
+}
+
+impl<K: Bounded + Neighbors, V> SparseGrid<K, V> {
+    /// Occupied neighbors of a point in any dimension, such as the 6 face or 26 surrounding
+    /// neighbors of a [`Vec3D`].
+    /// ```
+    /// use puzlib::{Neighborhood, SparseGrid, Vec3D};
+    /// let cubes: SparseGrid<Vec3D<i64>, ()> =
+    ///     [Vec3D(0, 0, 0), Vec3D(0, 0, 1), Vec3D(1, 1, 1)].into_iter().map(|p| (p, ())).collect();
+    /// assert_eq!(1, cubes.neighbors(&Vec3D(0, 0, 0), Neighborhood::Cardinals).count());
+    /// assert_eq!(2, cubes.neighbors(&Vec3D(0, 0, 0), Neighborhood::Compass).count());
+    /// ```
+    pub fn neighbors(&self, pos: &K, neighborhood: Neighborhood) -> impl Iterator<Item = (K, &V)> {
+        pos.neighbors(neighborhood)
+            .into_iter()
+            .filter_map(|n| self.get(&n).map(|v| (n, v)))
+    }
+}
+
+impl<K: Bounded, V> FromIterator<(K, V)> for SparseGrid<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut grid = Self::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<K: Bounded, V> Extend<(K, V)> for SparseGrid<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds_grow_and_shrink() {
+        let mut grid = SparseGrid::new();
+        grid.insert(Vec2D(0_i64, 0), 'o');
+        grid.insert(Vec2D(-3, 5), '#');
+        grid.insert(Vec2D(2, 1), '#');
+        assert_eq!(Some((Vec2D(-3, 0), Vec2D(2, 5))), grid.bounds());
+        grid.remove(&Vec2D(1, 1));
+        assert_eq!(3, grid.len());
+        grid.remove(&Vec2D(-3, 5));
+        assert_eq!(Some((Vec2D(0, 0), Vec2D(2, 1))), grid.bounds());
+        grid.remove(&Vec2D(0, 0));
+        grid.remove(&Vec2D(2, 1));
+        assert_eq!(None, grid.bounds());
+        assert!(grid.is_empty());
+    }
+
+    #[test]
+    fn test_bounds_3d() {
+        let grid: SparseGrid<Vec3D<i32>, ()> = [Vec3D(1, -2, 3), Vec3D(-1, 2, 0)]
+            .into_iter()
+            .map(|p| (p, ()))
+            .collect();
+        assert_eq!(Some((Vec3D(-1, -2, 0), Vec3D(1, 2, 3))), grid.bounds());
+    }
+
+    #[test]
+    fn test_neighbors_3d() {
+        let grid: SparseGrid<Vec3D<i64>, u8> = [
+            (Vec3D(1, 0, 0), 1),
+            (Vec3D(0, -1, 0), 2),
+            (Vec3D(1, 1, -1), 3),
+            (Vec3D(2, 0, 0), 4),
+        ]
+        .into_iter()
+        .collect();
+        let origin = Vec3D(0, 0, 0);
+        let mut faces = grid
+            .neighbors(&origin, Neighborhood::Cardinals)
+            .map(|(_, v)| *v)
+            .collect::<Vec<_>>();
+        faces.sort();
+        assert_eq!(vec![1, 2], faces);
+        assert_eq!(3, grid.neighbors(&origin, Neighborhood::Compass).count());
+        assert_eq!(
+            vec![(Vec3D(1, 0, 0), &1)],
+            grid.neighbors(&Vec3D(2, 0, 0), Neighborhood::Cardinals)
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_neighbors_and_render() {
+        let grid: SparseGrid<Vec2D<i64>, u8> =
+            [(Vec2D(-1, -1), 1), (Vec2D(-1, 0), 2), (Vec2D(0, 1), 3)]
+                .into_iter()
+                .collect();
+        let mut actual = grid
+            .compass_neighbors(&Vec2D(0, 0))
+            .map(|(_, v)| *v)
+            .collect::<Vec<_>>();
+        actual.sort();
+        assert_eq!(vec![1, 2, 3], actual);
+        assert_eq!(2, grid.cardinal_neighbors(&Vec2D(-1, 1)).count());
+        assert_eq!("12.\n..3\n", grid.render('.', |v| (b'0' + v) as char));
+    }
+}
@@ -0,0 +1,124 @@
+use std::ops::Index;
+
+use crate::{Dir, Vec2D};
+
+use super::Grid;
+
+/// An endless plane covered in copies of a grid, addressed with signed positions.
+/// Tile `Vec2D(0, 0)` is the grid itself. Build one with [`Grid::tiled`].
+/// ```
+/// use puzlib::{Grid, Vec2D};
+/// let grid = Grid::from_cells(vec!['a', 'b', 'c', 'd', 'e', 'f'], 3);
+/// let plane = grid.tiled();
+/// assert_eq!('f', plane[Vec2D(-1, -1)]);
+/// assert_eq!(Vec2D(1, 2), plane.wrap(Vec2D(-1, -1)));
+/// assert_eq!(Vec2D(-1, -1), plane.tile(Vec2D(-1, -1)));
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Tiled<'a, T> {
+    grid: &'a Grid<T>,
+}
+
+impl<T> Grid<T> {
+    /// View the grid as an endless tiled plane. Panics if the grid is empty.
+    pub fn tiled(&self) -> Tiled<'_, T> {
+        assert!(
+            self.width > 0 && self.height > 0,
+            "Cannot tile an empty grid"
+        );
+        Tiled { grid: self }
+    }
+}
+
+impl<'a, T> Tiled<'a, T> {
+    fn size(&self) -> Vec2D<i64> {
+        Vec2D(self.grid.height as i64, self.grid.width as i64)
+    }
+
+    /// The matching position in the base grid.
+    pub fn wrap(&self, pos: Vec2D<i64>) -> Vec2D<usize> {
+        let size = self.size();
+        Vec2D(
+            pos.0.rem_euclid(size.0) as usize,
+            pos.1.rem_euclid(size.1) as usize,
+        )
+    }
+
+    /// Which copy of the grid a position falls in, counted in tiles from the base grid.
+    pub fn tile(&self, pos: Vec2D<i64>) -> Vec2D<i64> {
+        let size = self.size();
+        Vec2D(pos.0.div_euclid(size.0), pos.1.div_euclid(size.1))
+    }
+
+    /// The cell at any position on the plane.
+    pub fn get(&self, pos: Vec2D<i64>) -> &'a T {
+        &self.grid[self.wrap(pos)]
+    }
+
+    /// Orthogonal neighbors (N, E, S, W) on the plane.
+    pub fn cardinals(&self, pos: Vec2D<i64>) -> [Vec2D<i64>; 4] {
+        Dir::<i64>::cardinals_unchecked(&pos)
+    }
+
+    /// Neighbors in all 8 directions (N, NE, E, SE, S, SW, W, NW) on the plane.
+    pub fn compass(&self, pos: Vec2D<i64>) -> [Vec2D<i64>; 8] {
+        Dir::<i64>::compass_unchecked(&pos)
+    }
+
+    /// Orthogonal neighbors (N, E, S, W) wrapped back into the base grid,
+    /// treating it as a torus.
+    /// ```
+    /// use puzlib::{Grid, Vec2D};
+    /// let grid = Grid::new(3, 2, '.');
+    /// let expected = [Vec2D(1, 0), Vec2D(0, 1), Vec2D(1, 0), Vec2D(0, 2)];
+    /// assert_eq!(expected, grid.tiled().wrapped_cardinals(Vec2D(0, 0)));
+    /// ```
+    pub fn wrapped_cardinals(&self, pos: Vec2D<usize>) -> [Vec2D<usize>; 4] {
+        self.cardinals(pos.map(|v| v as i64)).map(|n| self.wrap(n))
+    }
+
+    /// Neighbors in all 8 directions wrapped back into the base grid,
+    /// treating it as a torus.
+    pub fn wrapped_compass(&self, pos: Vec2D<usize>) -> [Vec2D<usize>; 8] {
+        self.compass(pos.map(|v| v as i64)).map(|n| self.wrap(n))
+    }
+}
+
+impl<T> Index<Vec2D<i64>> for Tiled<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: Vec2D<i64>) -> &Self::Output {
+        self.get(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap_and_tile() {
+        let grid = Grid::from_cells((0..6).collect(), 2);
+        let plane = grid.tiled();
+        assert_eq!(Vec2D(0, 1), plane.wrap(Vec2D(9, -3)));
+        assert_eq!(Vec2D(3, -2), plane.tile(Vec2D(9, -3)));
+        assert_eq!(1, plane[Vec2D(9, -3)]);
+        assert_eq!(5, *plane.get(Vec2D(-1, -1)));
+    }
+
+    #[test]
+    fn test_neighbors_cross_tiles() {
+        let grid = Grid::from_cells((0..4).collect(), 2);
+        let plane = grid.tiled();
+        let tiles = plane.cardinals(Vec2D(0, 1)).map(|n| plane.tile(n));
+        assert_eq!([Vec2D(-1, 0), Vec2D(0, 1), Vec2D(0, 0), Vec2D(0, 0)], tiles);
+        let wrapped = plane.wrapped_compass(Vec2D(1, 1));
+        assert_eq!(Vec2D(0, 0), wrapped[3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "empty grid")]
+    fn test_empty_grid() {
+        Grid::<u8>::from_cells(Vec::new(), 0).tiled();
+    }
+}
@@ -0,0 +1,179 @@
+use std::{
+    collections::hash_map::DefaultHasher,
+    hash::{Hash, Hasher},
+};
+
+use super::Grid;
+
+/// Rotations and reflections of a rectangular grid.
+/// Rotations are clockwise.
+/// ```
+/// use puzlib::Transform;
+/// let grid = vec![vec![1, 2], vec![3, 4]];
+/// assert_eq!(vec![vec![3, 1], vec![4, 2]], grid.rotate_90());
+/// assert_eq!(vec![vec![2, 1], vec![4, 3]], grid.flip_horizontal());
+/// ```
+pub trait Transform: Clone {
+    /// Swap rows and columns, reflecting across the main diagonal.
+    fn transpose(&self) -> Self;
+
+    /// Mirror left to right.
+    fn flip_horizontal(&self) -> Self;
+
+    /// Mirror top to bottom.
+    fn flip_vertical(&self) -> Self;
+
+    fn rotate_90(&self) -> Self {
+        self.transpose().flip_horizontal()
+    }
+
+    fn rotate_180(&self) -> Self {
+        self.flip_horizontal().flip_vertical()
+    }
+
+    fn rotate_270(&self) -> Self {
+        self.transpose().flip_vertical()
+    }
+
+    /// All 8 dihedral orientations: the four rotations followed by the
+    /// four rotations of the horizontal mirror image.
+    fn orientations(&self) -> [Self; 8] {
+        let flipped = self.flip_horizontal();
+        [
+            self.clone(),
+            self.rotate_90(),
+            self.rotate_180(),
+            self.rotate_270(),
+            flipped.rotate_90(),
+            flipped.rotate_180(),
+            flipped.rotate_270(),
+            flipped,
+        ]
+    }
+
+    /// The smallest orientation. Grids that are rotations or reflections of each
+    /// other share the same canonical form.
+    /// ```
+    /// use puzlib::Transform;
+    /// let a = vec![vec!['#', '.'], vec!['.', '.']];
+    /// let b = vec![vec!['.', '.'], vec!['.', '#']];
+    /// assert_eq!(a.canonical(), b.canonical());
+    /// ```
+    fn canonical(&self) -> Self
+    where
+        Self: Ord,
+    {
+        self.orientations().into_iter().min().unwrap()
+    }
+
+    /// A hash shared by every orientation, for deduplicating without `Ord`.
+    fn canonical_hash(&self) -> u64
+    where
+        Self: Hash,
+    {
+        self.orientations()
+            .iter()
+            .map(|o| {
+                let mut hasher = DefaultHasher::new();
+                o.hash(&mut hasher);
+                hasher.finish()
+            })
+            .min()
+            .unwrap()
+    }
+}
+
+impl<T: Clone> Transform for Vec<Vec<T>> {
+    fn transpose(&self) -> Self {
+        let width = self.first().map(|r| r.len()).unwrap_or(0);
+        (0..width)
+            .map(|col| self.iter().map(|row| row[col].clone()).collect())
+            .collect()
+    }
+
+    fn flip_horizontal(&self) -> Self {
+        self.iter()
+            .map(|row| row.iter().rev().cloned().collect())
+            .collect()
+    }
+
+    fn flip_vertical(&self) -> Self {
+        self.iter().rev().cloned().collect()
+    }
+}
+
+impl<T: Clone> Transform for Grid<T> {
+    fn transpose(&self) -> Self {
+        let cells = (0..self.width)
+            .flat_map(|col| self.rows().map(move |row| row[col].clone()))
+            .collect();
+        Grid::from_cells(cells, self.height)
+    }
+
+    fn flip_horizontal(&self) -> Self {
+        let cells = self
+            .rows()
+            .flat_map(|row| row.iter().rev().cloned())
+            .collect();
+        Grid::from_cells(cells, self.width)
+    }
+
+    fn flip_vertical(&self) -> Self {
+        let cells = self
+            .rows()
+            .rev()
+            .flat_map(|row| row.iter().cloned())
+            .collect();
+        Grid::from_cells(cells, self.width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    fn sample() -> Vec<Vec<u8>> {
+        vec![vec![1, 2, 3], vec![4, 5, 6]]
+    }
+
+    #[test]
+    fn test_rotations() {
+        let grid = sample();
+        assert_eq!(vec![vec![4, 1], vec![5, 2], vec![6, 3]], grid.rotate_90());
+        assert_eq!(vec![vec![6, 5, 4], vec![3, 2, 1]], grid.rotate_180());
+        assert_eq!(vec![vec![3, 6], vec![2, 5], vec![1, 4]], grid.rotate_270());
+        assert_eq!(grid, grid.rotate_90().rotate_270());
+    }
+
+    #[test]
+    fn test_grid_matches_vec() {
+        let rows = sample();
+        let grid: Grid<u8> = rows.clone().into();
+        for (g, r) in grid.orientations().into_iter().zip(rows.orientations()) {
+            assert_eq!(Grid::from(r), g);
+        }
+    }
+
+    #[test]
+    fn test_orientations_distinct() {
+        let unique = sample().orientations().into_iter().collect::<HashSet<_>>();
+        assert_eq!(8, unique.len());
+        let symmetric = vec![vec![1, 1], vec![1, 1]];
+        let unique = symmetric.orientations().into_iter().collect::<HashSet<_>>();
+        assert_eq!(1, unique.len());
+    }
+
+    #[test]
+    fn test_canonical_dedup() {
+        let grids = sample().orientations();
+        let canonical = grids.iter().map(|g| g.canonical()).collect::<HashSet<_>>();
+        assert_eq!(1, canonical.len());
+        let hashes = grids
+            .iter()
+            .map(|g| Grid::from(g.clone()).canonical_hash())
+            .collect::<HashSet<_>>();
+        assert_eq!(1, hashes.len());
+    }
+}
@@ -4,6 +4,9 @@ pub use combinatorics::*;
 pub mod graphs;
 pub use graphs::*;
 
+pub mod grid;
+pub use grid::*;
+
 pub mod measure;
 pub use measure::*;
 
@@ -1,4 +1,7 @@
-use std::ops::{Add, Sub};
+use std::{
+    ops::{Add, Sub},
+    str::FromStr,
+};
 
 macro_rules! checked_impl {
     ($trait_name:ident, $method:ident, $t:ty) => {
@@ -51,3 +54,20 @@ checked_impl!(CheckedSub, checked_sub, i32);
 checked_impl!(CheckedSub, checked_sub, i64);
 checked_impl!(CheckedSub, checked_sub, isize);
 checked_impl!(CheckedSub, checked_sub, i128);
+
+/// Primitive integer types that can be parsed from text.
+pub trait Integer: Copy + FromStr {
+    /// Whether the type can hold negative values.
+    const SIGNED: bool;
+}
+
+macro_rules! integer_impl {
+    ($signed:expr; $($t:ty) *) => ($(
+        impl Integer for $t {
+            const SIGNED: bool = $signed;
+        }
+    )*)
+}
+
+integer_impl!(false; u8 u16 u32 u64 usize u128);
+integer_impl!(true; i8 i16 i32 i64 isize i128);
@@ -0,0 +1,198 @@
+use std::ops::Index;
+
+use crate::Vec2D;
+
+use super::{FileReader, ReadError, error::locate, grid_text, or_panic, records};
+
+/// A rectangular grid of bytes viewed directly in the input text.
+/// Rows are addressed by offset into the text so no per-row allocation is made.
+/// ```
+/// use puzlib::{ByteGrid, Vec2D};
+/// let grid = ByteGrid::new("#..\n.#.\n").unwrap();
+/// assert_eq!((3, 2), (grid.width(), grid.height()));
+/// assert_eq!(b'#', grid[Vec2D(1, 1)]);
+/// assert_eq!(Some(Vec2D(0, 0)), grid.find(b'#'));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteGrid<'a> {
+    data: &'a [u8],
+    width: usize,
+    height: usize,
+}
+
+impl<'a> ByteGrid<'a> {
+    /// View `text` as a grid. Blank lines around the grid are ignored and every
+    /// row must have the same length. Rows are split on `\n` only, so a raw `\r` stays in its row.
+    pub fn new(text: &'a str) -> Result<Self, ReadError> {
+        let (leading, text) = {
+            let grid = grid_text(text);
+            (locate(text, grid).0 - 1, grid)
+        };
+        let mut rows = text.split('\n');
+        let width = rows.next().map(|r| r.len()).unwrap_or(0);
+        if let Some((idx, row)) = rows.enumerate().find(|(_, r)| r.len() != width) {
+            return Err(ReadError::RowLength {
+                line: leading + idx + 2,
+                expected: width,
+                found: row.len(),
+            });
+        }
+        let height = if text.is_empty() {
+            0
+        } else {
+            text.len() / (width + 1) + 1
+        };
+        Ok(Self {
+            data: text.as_bytes(),
+            width,
+            height,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// A single row without its line ending.
+    pub fn row(&self, row: usize) -> &'a [u8] {
+        let start = row * (self.width + 1);
+        &self.data[start..start + self.width]
+    }
+
+    /// All rows, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &'a [u8]> + '_ {
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    /// The byte at a row/col position or `None` if out of bounds.
+    pub fn get(&self, pos: Vec2D<usize>) -> Option<u8> {
+        let Vec2D(row, col) = pos;
+        if row < self.height && col < self.width {
+            Some(self.data[row * (self.width + 1) + col])
+        } else {
+            None
+        }
+    }
+
+    /// Row/col position and byte of every cell.
+    pub fn iter(&self) -> impl Iterator<Item = (Vec2D<usize>, u8)> + '_ {
+        self.rows().enumerate().flat_map(|(row, bytes)| {
+            bytes
+                .iter()
+                .enumerate()
+                .map(move |(col, b)| (Vec2D(row, col), *b))
+        })
+    }
+
+    /// Position of the first occurrence of a byte, scanning row by row.
+    pub fn find(&self, byte: u8) -> Option<Vec2D<usize>> {
+        let idx = self.data.iter().position(|b| *b == byte && *b != b'\n')?;
+        Some(Vec2D(idx / (self.width + 1), idx % (self.width + 1)))
+    }
+}
+
+impl Index<Vec2D<usize>> for ByteGrid<'_> {
+    type Output = u8;
+
+    fn index(&self, index: Vec2D<usize>) -> &Self::Output {
+        let Vec2D(row, col) = index;
+        assert!(
+            row < self.height && col < self.width,
+            "Position {index} is outside a {}x{} grid",
+            self.height,
+            self.width
+        );
+        &self.data[row * (self.width + 1) + col]
+    }
+}
+
+impl FileReader {
+    /// Non-blank lines as bytes. Borrowed counterpart of [`read_lines`](super::read_lines).
+    pub fn byte_lines(&self) -> impl Iterator<Item = &[u8]> {
+        self.lines().map(str::as_bytes)
+    }
+
+    /// Blank-line separated records as slices of the contents.
+    /// Borrowed counterpart of [`read_string_records`](super::read_string_records).
+    pub fn string_records(&self) -> impl Iterator<Item = &str> {
+        records(&self.contents)
+    }
+
+    /// The contents as a byte grid. Borrowed counterpart of [`read_grid`](super::read_grid).
+    pub fn byte_grid(&self) -> ByteGrid<'_> {
+        or_panic(self.try_byte_grid())
+    }
+
+    /// Fallible version of [`FileReader::byte_grid`].
+    pub fn try_byte_grid(&self) -> Result<ByteGrid<'_>, ReadError> {
+        self.grid_view(&self.contents)
+    }
+
+    /// Each blank-line separated record as a byte grid.
+    /// Borrowed counterpart of [`read_grid_records`](super::read_grid_records).
+    /// ```
+    /// let reader = puzlib::FileReader::new("#.\n.#\n\n###\n");
+    /// let widths = reader.byte_grid_records().map(|g| g.width()).collect::<Vec<_>>();
+    /// assert_eq!(vec![2, 3], widths);
+    /// ```
+    pub fn byte_grid_records(&self) -> impl Iterator<Item = ByteGrid<'_>> {
+        self.try_byte_grid_records().map(or_panic)
+    }
+
+    /// Fallible version of [`FileReader::byte_grid_records`].
+    pub fn try_byte_grid_records(&self) -> impl Iterator<Item = Result<ByteGrid<'_>, ReadError>> {
+        records(&self.contents).map(|record| self.grid_view(record))
+    }
+
+    /// Grid view of a slice of the contents, reporting errors against the full input.
+    fn grid_view<'a>(&'a self, text: &'a str) -> Result<ByteGrid<'a>, ReadError> {
+        let before = locate(&self.contents, text).0 - 1;
+        ByteGrid::new(text).map_err(|e| e.offset_lines(self.line_offset + before))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_byte_grid_rows() {
+        let grid = ByteGrid::new("\n\nab\ncd\nef\n\n").unwrap();
+        assert_eq!(3, grid.height());
+        assert_eq!(b"cd", grid.row(1));
+        assert_eq!(Some(b'f'), grid.get(Vec2D(2, 1)));
+        assert_eq!(None, grid.get(Vec2D(0, 2)));
+        assert_eq!(Some(Vec2D(2, 0)), grid.find(b'e'));
+        assert_eq!(6, grid.iter().count());
+    }
+
+    #[test]
+    fn test_byte_grid_ragged() {
+        let reader = FileReader::new("..\n..\n\n...\n..\n.\n");
+        let actual = reader.try_byte_grid_records().collect::<Vec<_>>();
+        assert!(actual[0].is_ok());
+        assert_eq!(
+            Err(ReadError::RowLength {
+                line: 5,
+                expected: 3,
+                found: 2
+            }),
+            actual[1]
+        );
+    }
+
+    #[test]
+    fn test_borrowed_records() {
+        let reader = FileReader::new("a\nb\n\nc\n");
+        let records = reader.string_records().collect::<Vec<_>>();
+        assert_eq!(vec!["a\nb", "c"], records);
+        assert_eq!(
+            vec![b"a", b"b", b"c"],
+            reader.byte_lines().collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,185 @@
+use std::{
+    any::type_name,
+    fmt::Display,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// Errors reported by the fallible (`try_`) reader functions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReadError {
+    /// The input could not be read.
+    Io {
+        path: PathBuf,
+        kind: ErrorKind,
+        message: String,
+    },
+    /// Some text in the input could not be converted to the target type.
+    /// Line and column are 1-based.
+    Parse {
+        line: usize,
+        column: usize,
+        text: String,
+        target: &'static str,
+    },
+    /// A line did not match the expected template.
+    /// `found` is the rest of the line from the first mismatching character.
+    Mismatch {
+        line: usize,
+        column: usize,
+        expected: String,
+        found: String,
+    },
+    /// A line produced a different number of fields than the target type takes.
+    FieldCount {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A grid row has a different length than the first row.
+    RowLength {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// The input has fewer blank-line separated sections than requested.
+    MissingSection { expected: usize, found: usize },
+}
+
+impl ReadError {
+    /// Build an I/O error for the given path.
+    pub fn io<P: AsRef<Path>>(path: P, err: std::io::Error) -> Self {
+        Self::Io {
+            path: path.as_ref().to_path_buf(),
+            kind: err.kind(),
+            message: err.to_string(),
+        }
+    }
+
+    /// Build a parse error for `text`, which must be a slice of `contents`.
+    pub fn parse<T>(contents: &str, text: &str) -> Self {
+        let (line, column) = locate(contents, text);
+        Self::Parse {
+            line,
+            column,
+            text: text.to_string(),
+            target: type_name::<T>(),
+        }
+    }
+
+    /// Move the reported line down by `lines`, for errors found in part of a larger input.
+    pub(crate) fn offset_lines(mut self, lines: usize) -> Self {
+        if let Self::Parse { line, .. }
+        | Self::Mismatch { line, .. }
+        | Self::FieldCount { line, .. }
+        | Self::RowLength { line, .. } = &mut self
+        {
+            *line += lines;
+        }
+        self
+    }
+
+    /// Line of the input the error occurred on, if known.
+    pub fn line(&self) -> Option<usize> {
+        match self {
+            Self::Io { .. } | Self::MissingSection { .. } => None,
+            Self::Parse { line, .. }
+            | Self::Mismatch { line, .. }
+            | Self::FieldCount { line, .. }
+            | Self::RowLength { line, .. } => Some(*line),
+        }
+    }
+}
+
+impl Display for ReadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Io { path, message, .. } => {
+                write!(f, "could not read {}: {message}", path.display())
+            }
+            Self::Parse {
+                line,
+                column,
+                text,
+                target,
+            } => write!(
+                f,
+                "line {line}, column {column}: could not parse {text:?} as {target}"
+            ),
+            Self::Mismatch {
+                line,
+                column,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line}, column {column}: expected {expected:?} but found {found:?}"
+            ),
+            Self::FieldCount {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line}: expected {expected} fields but found {found}"
+            ),
+            Self::RowLength {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line}: expected a row of length {expected} but found {found}"
+            ),
+            Self::MissingSection { expected, found } => {
+                write!(f, "expected {expected} sections but found {found}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReadError {}
+
+/// Parse `text` (a slice of `contents`) reporting its position on failure.
+pub(crate) fn parse_in<T: FromStr>(contents: &str, text: &str) -> Result<T, ReadError> {
+    text.parse::<T>()
+        .map_err(|_| ReadError::parse::<T>(contents, text))
+}
+
+/// 1-based line and column of `text` within `contents`.
+/// Falls back to the start of the input if `text` is not a slice of `contents`.
+pub(crate) fn locate(contents: &str, text: &str) -> (usize, usize) {
+    let start = contents.as_ptr() as usize;
+    let offset = (text.as_ptr() as usize)
+        .checked_sub(start)
+        .filter(|o| *o <= contents.len())
+        .unwrap_or(0);
+    let before = &contents[..offset];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let column = before[line_start..].chars().count() + 1;
+    (line, column)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_locate() {
+        let contents = "12\n34,ab\n";
+        let text = &contents[6..8];
+        assert_eq!((2, 4), locate(contents, text));
+    }
+
+    #[test]
+    fn test_parse_error_display() {
+        let contents = "1\nx2\n";
+        let err = parse_in::<u8>(contents, &contents[2..4]).unwrap_err();
+        assert_eq!(
+            "line 2, column 1: could not parse \"x2\" as u8",
+            err.to_string()
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use crate::Integer;
+
+use super::{ReadError, error::parse_in};
+
+/// How a `-` directly in front of a digit is treated when extracting integers.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Minus {
+    /// A sign for signed target types and a separator for unsigned ones.
+    #[default]
+    Auto,
+    /// Always a sign, so `3-7` is `3` and `-7`.
+    Sign,
+    /// Always a separator, so `3-7` is `3` and `7`.
+    Separator,
+}
+
+/// Pull every integer out of some text, ignoring whatever surrounds them.
+/// Panics if a number does not fit in T.
+/// ```
+/// let actual = puzlib::extract_ints::<i64>("Sensor at x=2, y=-18: beacon at x=-2, y=15");
+/// assert_eq!(vec![2, -18, -2, 15], actual);
+/// let actual = puzlib::extract_ints::<u8>("3-7,10-12");
+/// assert_eq!(vec![3, 7, 10, 12], actual);
+/// ```
+pub fn extract_ints<T: Integer>(text: &str) -> Vec<T> {
+    extract_ints_with(text, Minus::Auto)
+}
+
+/// [`extract_ints`] with explicit handling of `-`.
+/// ```
+/// use puzlib::{Minus, extract_ints_with};
+/// assert_eq!(vec![3, 7], extract_ints_with::<i32>("3-7", Minus::Separator));
+/// assert_eq!(vec![3, -7], extract_ints_with::<i32>("3-7", Minus::Sign));
+/// ```
+pub fn extract_ints_with<T: Integer>(text: &str, minus: Minus) -> Vec<T> {
+    try_extract_ints_with(text, minus).unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Fallible version of [`extract_ints_with`]. Fails if a number does not fit in T.
+pub fn try_extract_ints_with<T: Integer>(text: &str, minus: Minus) -> Result<Vec<T>, ReadError> {
+    int_tokens(text, minus, T::SIGNED)
+        .map(|token| parse_in(text, token))
+        .collect()
+}
+
+/// Slices of `text` that form integers.
+pub(crate) fn int_tokens(text: &str, minus: Minus, signed: bool) -> impl Iterator<Item = &str> {
+    let signs = match minus {
+        Minus::Auto => signed,
+        Minus::Sign => true,
+        Minus::Separator => false,
+    };
+    let bytes = text.as_bytes();
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        while pos < bytes.len() {
+            let start = pos;
+            let negative = signs
+                && bytes[pos] == b'-'
+                && bytes.get(pos + 1).is_some_and(|b| b.is_ascii_digit());
+            if negative {
+                pos += 1;
+            }
+            if bytes[pos].is_ascii_digit() {
+                while pos < bytes.len() && bytes[pos].is_ascii_digit() {
+                    pos += 1;
+                }
+                return Some(&text[start..pos]);
+            }
+            pos += 1;
+        }
+        None
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extract_wide_types() {
+        let expected = vec![-170141183460469231731687303715884105728_i128, 12];
+        let actual = extract_ints::<i128>("min -170141183460469231731687303715884105728 and 12");
+        assert_eq!(expected, actual);
+        let expected = vec![u128::MAX];
+        let actual = extract_ints::<u128>("max=340282366920938463463374607431768211455");
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_extract_dashes() {
+        assert_eq!(vec![1, -2, 3], extract_ints::<i8>("a1--2 -b3-"));
+        assert_eq!(vec![1, 2, 3], extract_ints::<u8>("a1--2 -b3-"));
+    }
+
+    #[test]
+    fn test_extract_overflow() {
+        let expected = ReadError::Parse {
+            line: 2,
+            column: 3,
+            text: "256".into(),
+            target: "u8",
+        };
+        let actual = try_extract_ints_with::<u8>("1\nx 256", Minus::Auto).unwrap_err();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_unsigned_with_sign_fails() {
+        assert!(try_extract_ints_with::<u32>("5 -1", Minus::Sign).is_err());
+    }
+}
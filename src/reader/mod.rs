@@ -0,0 +1,851 @@
+use std::{
+    borrow::Cow,
+    fmt::{Debug, Display},
+    path::Path,
+    str::FromStr,
+};
+
+use crate::{Grid, Integer};
+
+mod bytes;
+pub use bytes::ByteGrid;
+
+mod error;
+pub use error::ReadError;
+use error::{locate, parse_in};
+
+mod ints;
+pub use ints::{Minus, extract_ints, extract_ints_with, try_extract_ints_with};
+
+mod sections;
+pub use sections::FromSection;
+
+mod source;
+pub use source::{Input, InputSource, Raw};
+
+mod stream;
+pub use stream::LineStream;
+
+mod template;
+pub use template::{Field, FromFields, Template};
+
+/// Read the input file to various collectioins. Input can be a string or a path.
+///
+/// Contents are [normalized](normalize) so `\r\n` line endings behave like plain `\n`.
+/// Lines holding only whitespace count as blank when the contents are split into lines,
+/// records, sections or grid records, and around a grid. Between the rows of a single
+/// grid they are kept as rows of spaces. Use [`FileReader::from_source_raw`]
+/// to keep the input exactly as read.
+pub struct FileReader {
+    contents: String,
+    /// Lines before the contents when this reader is a section of a larger input.
+    line_offset: usize,
+}
+
+impl FileReader {
+    /// Read either the contents of a file path or if a string is passed in,
+    /// use that as the file contents. See [`InputSource::infer`] for how the two are told apart.
+    pub fn new<S: AsRef<Path> + Display>(path: S) -> Self {
+        or_panic(Self::try_new(path))
+    }
+
+    /// Fallible version of [`FileReader::new`].
+    pub fn try_new<S: AsRef<Path> + Display>(path: S) -> Result<Self, ReadError> {
+        Self::from_source(InputSource::infer(path)?)
+    }
+
+    /// Read the contents of an explicit source.
+    pub fn from_source(source: InputSource) -> Result<Self, ReadError> {
+        Ok(Self::from_str_literal(source.read()?))
+    }
+
+    /// Read the contents of an explicit source without normalizing them.
+    pub fn from_source_raw(source: InputSource) -> Result<Self, ReadError> {
+        Ok(Self {
+            contents: source.read()?,
+            line_offset: 0,
+        })
+    }
+
+    /// Read the contents of a file. Never falls back to treating the path as text.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ReadError> {
+        Self::from_source(InputSource::File(path.as_ref().to_path_buf()))
+    }
+
+    /// Use the text itself as the contents, even if it names an existing file.
+    /// Line endings are [normalized](normalize).
+    pub fn from_str_literal<S: Into<String>>(text: S) -> Self {
+        Self {
+            contents: normalize_owned(text.into()),
+            line_offset: 0,
+        }
+    }
+
+    /// Read everything from standard input.
+    pub fn from_stdin() -> Result<Self, ReadError> {
+        Self::from_source(InputSource::Stdin)
+    }
+
+    /// Use text compiled into the binary as the contents.
+    /// ```
+    /// let reader = puzlib::FileReader::from_embedded("1\n2\n");
+    /// assert_eq!(2, reader.lines().count());
+    /// ```
+    pub fn from_embedded(text: &'static str) -> Self {
+        Self::from_str_literal(text)
+    }
+
+    /// Raw file contents.
+    pub fn contents(&self) -> &str {
+        &self.contents
+    }
+
+    /// Iterator of the non-blank lines in the file
+    pub fn lines(&self) -> impl Iterator<Item = &str> {
+        self.contents.lines().filter(|s| !is_blank(s))
+    }
+
+    /// Every integer in the contents, ignoring any surrounding text.
+    /// `-` is a sign for signed types and a separator for unsigned ones.
+    /// ```
+    /// let reader = puzlib::FileReader::new("p=0,4 v=3,-3\np=6,3 v=-1,-3\n");
+    /// assert_eq!(9, reader.ints::<i32>().sum());
+    /// ```
+    pub fn ints<T: Integer>(&self) -> impl Iterator<Item = T> {
+        ints::int_tokens(&self.contents, Minus::Auto, T::SIGNED)
+            .map(|token| or_panic(self.parse(token)))
+    }
+
+    /// The integers of each non-blank line.
+    pub fn ints_per_line<T: Integer>(&self) -> impl Iterator<Item = Vec<T>> {
+        self.ints_per_line_with(Minus::Auto)
+    }
+
+    /// The integers of each non-blank line with explicit handling of `-`.
+    /// ```
+    /// use puzlib::{FileReader, Minus};
+    /// let reader = FileReader::new("2-4,6-8\n2-3,4-5\n");
+    /// let ranges = reader.ints_per_line_with::<i32>(Minus::Separator).collect::<Vec<_>>();
+    /// assert_eq!(vec![vec![2, 4, 6, 8], vec![2, 3, 4, 5]], ranges);
+    /// ```
+    pub fn ints_per_line_with<T: Integer>(&self, minus: Minus) -> impl Iterator<Item = Vec<T>> {
+        self.try_ints_per_line_with(minus).map(or_panic)
+    }
+
+    /// Fallible version of [`FileReader::ints_per_line_with`].
+    /// A line fails if any of its numbers does not fit in T.
+    pub fn try_ints_per_line_with<T: Integer>(
+        &self,
+        minus: Minus,
+    ) -> impl Iterator<Item = Result<Vec<T>, ReadError>> {
+        self.lines().map(move |line| {
+            ints::int_tokens(line, minus, T::SIGNED)
+                .map(|token| self.parse(token))
+                .collect()
+        })
+    }
+
+    /// Parse every non-blank line with a [`Template`], panicking on the first bad line.
+    /// ```
+    /// let reader = puzlib::FileReader::new("move 1 from 2 to 3\nmove 4 from 5 to 6\n");
+    /// let moves = reader.parse_lines::<(u8, u8, u8)>("move {} from {} to {}").collect::<Vec<_>>();
+    /// assert_eq!(vec![(1, 2, 3), (4, 5, 6)], moves);
+    /// ```
+    pub fn parse_lines<T: FromFields>(&self, template: &str) -> impl Iterator<Item = T> {
+        self.try_parse_lines(template).map(or_panic)
+    }
+
+    /// Fallible version of [`FileReader::parse_lines`]. Errors report the line
+    /// and column of the first character that does not fit the template.
+    pub fn try_parse_lines<T: FromFields>(
+        &self,
+        template: &str,
+    ) -> impl Iterator<Item = Result<T, ReadError>> {
+        let template = Template::new(template);
+        self.contents
+            .lines()
+            .enumerate()
+            .filter(|(_, l)| !is_blank(l))
+            .map(move |(idx, l)| template.parse_line(l, self.line_offset + idx + 1))
+    }
+
+    /// Convert the contents to an iterator or chars, ignoring new-line characters and blank lines.
+    pub fn chars(&self) -> impl Iterator<Item = char> {
+        line_chars(&self.contents)
+    }
+
+    /// Read the contents as a single line separated by a predicate.
+    pub fn line_sep<'a, S: AsRef<str>>(&'a self, sep: &'a S) -> impl Iterator<Item = &'a str> {
+        self.contents.trim().split(sep.as_ref())
+    }
+
+    /// Read chucks of blank-line separated records where each line is converted to type N.
+    /// For example:
+    /// ```
+    /// let actual = puzlib::FileReader::new("1234\n4567\n\n3423\n3543\n").records::<usize>().collect::<Vec<_>>();
+    /// let expected = vec![vec![1234, 4567], vec![3423, 3543]];
+    /// ```
+    pub fn records<N: FromStr + Display>(&self) -> impl Iterator<Item = Vec<N>>
+    where
+        <N as FromStr>::Err: Debug,
+    {
+        self.try_records().map(or_panic)
+    }
+
+    /// Fallible version of [`FileReader::records`]. Each record is parsed independently
+    /// so a bad record can be reported or skipped without losing the rest.
+    /// ```
+    /// let reader = puzlib::FileReader::new("12\n34\n\n5x\n\n78\n");
+    /// let good = reader.try_records::<u8>().filter_map(Result::ok).collect::<Vec<_>>();
+    /// assert_eq!(vec![vec![12, 34], vec![78]], good);
+    /// ```
+    pub fn try_records<N: FromStr>(&self) -> impl Iterator<Item = Result<Vec<N>, ReadError>> {
+        records(&self.contents).map(|s| {
+            s.lines()
+                .map(|n| self.parse(n))
+                .collect::<Result<Vec<N>, _>>()
+        })
+    }
+
+    /// Read the grid to pairs of row/col coordinates and char ignoring anything specified.
+    pub fn read_grid<T, R: Copy + Fn((usize, usize)) -> T>(
+        &self,
+        ignores: &[char],
+        return_type: R,
+    ) -> impl Iterator<Item = (T, char)> {
+        self.contents
+            .lines()
+            .enumerate()
+            .flat_map(move |(row, line)| {
+                line.chars().enumerate().filter_map(move |(col, ch)| {
+                    if ignores.contains(&ch) {
+                        None
+                    } else {
+                        Some((return_type((row, col)), ch))
+                    }
+                })
+            })
+    }
+
+    /// Read the grid to pairs of row/col coordinates and type N ignoring anything specified.
+    pub fn read_grid_convert<N, C: Copy + Fn(char) -> N, T, R: Copy + Fn((usize, usize)) -> T>(
+        &self,
+        ignores: &[char],
+        return_type: R,
+        convert: C,
+    ) -> impl Iterator<Item = (T, N)> {
+        self.contents
+            .lines()
+            .enumerate()
+            .flat_map(move |(row, line)| {
+                line.chars().enumerate().filter_map(move |(col, ch)| {
+                    if ignores.contains(&ch) {
+                        None
+                    } else {
+                        Some((return_type((row, col)), convert(ch)))
+                    }
+                })
+            })
+    }
+
+    /// The contents as a [`Grid`] of chars. Blank lines around the grid are ignored.
+    /// ```
+    /// let grid = puzlib::FileReader::new("S.#\n..E\n").grid();
+    /// assert_eq!(Some(puzlib::Vec2D(1, 2)), grid.find(&'E'));
+    /// ```
+    pub fn grid(&self) -> Grid<char> {
+        self.grid_with(|c| c)
+    }
+
+    /// The contents as a [`Grid`] with each char converted.
+    pub fn grid_with<U>(&self, convert: impl FnMut(char) -> U) -> Grid<U> {
+        or_panic(self.try_grid_with(convert))
+    }
+
+    /// Fallible version of [`FileReader::grid_with`]. Fails if the rows have different lengths.
+    pub fn try_grid_with<U>(
+        &self,
+        mut convert: impl FnMut(char) -> U,
+    ) -> Result<Grid<U>, ReadError> {
+        let text = grid_text(&self.contents);
+        let leading = locate(&self.contents, text).0 - 1;
+        let mut width = None;
+        let mut cells = Vec::new();
+        for (idx, row) in text.lines().enumerate() {
+            let before = cells.len();
+            cells.extend(row.chars().map(&mut convert));
+            let len = cells.len() - before;
+            if *width.get_or_insert(len) != len {
+                return Err(ReadError::RowLength {
+                    line: self.line_offset + leading + idx + 1,
+                    expected: width.unwrap_or_default(),
+                    found: len,
+                });
+            }
+        }
+        Ok(Grid::from_cells(cells, width.unwrap_or_default()))
+    }
+
+    /// Parse a slice of the contents, reporting errors against the full input.
+    fn parse<T: FromStr>(&self, text: &str) -> Result<T, ReadError> {
+        parse_in(&self.contents, text).map_err(|e| e.offset_lines(self.line_offset))
+    }
+}
+
+impl FromStr for FileReader {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_path(s).map_err(|e| e.to_string())
+    }
+}
+
+/// Unwrap a reader result, panicking with the error's message.
+fn or_panic<T>(res: Result<T, ReadError>) -> T {
+    res.unwrap_or_else(|e| panic!("{e}"))
+}
+
+/// Gather a string of text or file name to a string.
+/// See [`InputSource::infer`] for how the two are told apart.
+pub fn contents<T: Input>(path: T) -> String {
+    or_panic(try_contents(path))
+}
+
+/// Fallible version of [`contents`].
+pub fn try_contents<T: Input>(path: T) -> Result<String, ReadError> {
+    path.read_input()
+}
+
+/// Gather a string of text or file name to a string without normalizing it.
+pub fn raw_contents<T: AsRef<Path> + Display>(path: T) -> String {
+    or_panic(try_raw_contents(path))
+}
+
+/// Fallible version of [`raw_contents`].
+pub fn try_raw_contents<T: AsRef<Path> + Display>(path: T) -> Result<String, ReadError> {
+    InputSource::infer(path)?.read()
+}
+
+/// Convert `\r\n` line endings to `\n`. Lone `\r` characters and whitespace-only
+/// lines are kept, so line numbers are unchanged. Borrows the text when it is already normalized.
+/// ```
+/// assert_eq!("1\n \t\n2\r3\n", puzlib::normalize("1\r\n \t\r\n2\r3\r\n"));
+/// ```
+pub fn normalize(text: &str) -> Cow<'_, str> {
+    if text.contains("\r\n") {
+        Cow::Owned(text.replace("\r\n", "\n"))
+    } else {
+        Cow::Borrowed(text)
+    }
+}
+
+/// [`normalize`] owned text, keeping its allocation when nothing changes.
+fn normalize_owned(text: String) -> String {
+    let normalized = match normalize(&text) {
+        Cow::Borrowed(_) => None,
+        Cow::Owned(normalized) => Some(normalized),
+    };
+    normalized.unwrap_or(text)
+}
+
+/// Whether a line is empty or only whitespace.
+fn is_blank(line: &str) -> bool {
+    line.trim().is_empty()
+}
+
+/// Chars of every non-blank line, without line breaks.
+fn line_chars(text: &str) -> impl Iterator<Item = char> {
+    text.split('\n')
+        .filter(|l| !is_blank(l))
+        .flat_map(str::chars)
+}
+
+/// The part of `text` holding a single grid. Blank lines around it are dropped,
+/// but a row of spaces between other rows is kept.
+fn grid_text(text: &str) -> &str {
+    let mut text = text;
+    while let Some((first, rest)) = text.split_once('\n')
+        && is_blank(first)
+    {
+        text = rest;
+    }
+    while let Some((rest, last)) = text.rsplit_once('\n')
+        && is_blank(last)
+    {
+        text = rest;
+    }
+    if is_blank(text) { &text[..0] } else { text }
+}
+
+/// Slices of `text` between runs of blank lines, without their final line break.
+fn records(text: &str) -> impl Iterator<Item = &str> {
+    let mut offset = 0;
+    let mut lines = text
+        .split_inclusive('\n')
+        .map(move |line| {
+            let start = offset;
+            offset += line.len();
+            let line = line.trim_end_matches('\n');
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            (start, start + line.len(), is_blank(line))
+        })
+        .peekable();
+    std::iter::from_fn(move || {
+        while lines.next_if(|(_, _, blank)| *blank).is_some() {}
+        let (start, mut end, _) = lines.next()?;
+        while let Some((_, line_end, _)) = lines.next_if(|(_, _, blank)| !blank) {
+            end = line_end;
+        }
+        Some(&text[start..end])
+    })
+}
+
+/// Read the text of a file to a vec of strings
+pub fn read_lines<T: Input>(path: T) -> Vec<String> {
+    or_panic(try_read_lines(path))
+}
+
+/// Fallible version of [`read_lines`].
+pub fn try_read_lines<T: Input>(path: T) -> Result<Vec<String>, ReadError> {
+    Ok(try_contents(path)?
+        .lines()
+        .filter(|s| !is_blank(s))
+        .map(|s| s.to_string())
+        .collect())
+}
+
+/// Reads records that are line delineated.
+/// For example:
+/// 1234
+/// 4567
+///
+/// 3423
+/// 2543
+pub fn read_number_records<T: Input, U: FromStr>(path: T) -> Vec<Vec<U>>
+where
+    <U as FromStr>::Err: Debug,
+{
+    or_panic(try_read_number_records(path))
+}
+
+/// Fallible version of [`read_number_records`].
+pub fn try_read_number_records<T: Input, U: FromStr>(path: T) -> Result<Vec<Vec<U>>, ReadError> {
+    let contents = try_contents(path)?;
+    records(&contents)
+        .map(|s| s.lines().map(|num| parse_in(&contents, num)).collect())
+        .collect()
+}
+
+/// Return records split by blank lines.
+pub fn read_string_records<T: Input>(path: T) -> Vec<String> {
+    or_panic(try_read_string_records(path))
+}
+
+/// Fallible version of [`read_string_records`].
+pub fn try_read_string_records<T: Input>(path: T) -> Result<Vec<String>, ReadError> {
+    Ok(records(&try_contents(path)?)
+        .map(|s| s.to_string())
+        .collect())
+}
+
+/// Reads the text of a file to a vector of numbers.
+pub fn read_numbers<T: Input, U: FromStr>(path: T) -> Vec<U>
+where
+    <U as FromStr>::Err: Debug,
+{
+    or_panic(try_read_numbers(path))
+}
+
+/// Fallible version of [`read_numbers`].
+/// ```
+/// let err = puzlib::try_read_numbers::<_, u32>("12\n\n3a\n").unwrap_err();
+/// assert_eq!(Some(3), err.line());
+/// ```
+pub fn try_read_numbers<T: Input, U: FromStr>(path: T) -> Result<Vec<U>, ReadError> {
+    let contents = try_contents(path)?;
+    contents
+        .lines()
+        .filter(|s| !is_blank(s))
+        .map(|l| parse_in(&contents, l))
+        .collect()
+}
+
+/// Reads the text of a file to a vector of vector of numbers.
+pub fn read_number_lists<T: Input, U: FromStr>(path: T, sep: &str) -> Vec<Vec<U>>
+where
+    <U as FromStr>::Err: Debug,
+{
+    or_panic(try_read_number_lists(path, sep))
+}
+
+/// Fallible version of [`read_number_lists`].
+pub fn try_read_number_lists<T: Input, U: FromStr>(
+    path: T,
+    sep: &str,
+) -> Result<Vec<Vec<U>>, ReadError> {
+    let contents = try_contents(path)?;
+    contents
+        .lines()
+        .filter(|s| !is_blank(s))
+        .map(|l| l.split(sep).map(|n| parse_in(&contents, n)).collect())
+        .collect()
+}
+
+/// Reads the file to a list of chars.
+pub fn read_line<T: Input>(path: T) -> Vec<char> {
+    or_panic(try_read_line(path))
+}
+
+/// Fallible version of [`read_line`].
+pub fn try_read_line<T: Input>(path: T) -> Result<Vec<char>, ReadError> {
+    Ok(line_chars(&try_contents(path)?).collect())
+}
+
+/// Reads a single line file to a list breaking on a separator.
+pub fn read_line_sep<T: Input>(path: T, sep: &str) -> Vec<String> {
+    or_panic(try_read_line_sep(path, sep))
+}
+
+/// Fallible version of [`read_line_sep`].
+pub fn try_read_line_sep<T: Input>(path: T, sep: &str) -> Result<Vec<String>, ReadError> {
+    Ok(try_contents(path)?
+        .trim()
+        .split(sep)
+        .map(|s| s.into())
+        .collect())
+}
+
+/// Reads the file to a list of chars.
+pub fn read_line_record<T: Input, U: FromStr>(path: T) -> Vec<U>
+where
+    <U as FromStr>::Err: Debug,
+{
+    or_panic(try_read_line_record(path))
+}
+
+/// Fallible version of [`read_line_record`].
+pub fn try_read_line_record<T: Input, U: FromStr>(path: T) -> Result<Vec<U>, ReadError> {
+    let contents = try_contents(path)?;
+    contents
+        .trim()
+        .split(",")
+        .map(|v| parse_in(&contents, v))
+        .collect()
+}
+
+/// Reads the file to a grid (vec of vec) of chars
+pub fn read_grid<T: Input>(path: T) -> Vec<Vec<char>> {
+    or_panic(try_read_grid(path))
+}
+
+/// Fallible version of [`read_grid`].
+pub fn try_read_grid<T: Input>(path: T) -> Result<Vec<Vec<char>>, ReadError> {
+    Ok(grid_text(&try_contents(path)?)
+        .lines()
+        .map(|l| l.chars().collect())
+        .collect())
+}
+
+/// Reads the file to a grid (vec of vec) of u8
+pub fn read_grid_numbers<T: Input>(path: T) -> Vec<Vec<u8>> {
+    or_panic(try_read_grid_numbers(path))
+}
+
+/// Fallible version of [`read_grid_numbers`]. Every character must be a decimal digit.
+pub fn try_read_grid_numbers<T: Input>(path: T) -> Result<Vec<Vec<u8>>, ReadError> {
+    let contents = try_contents(path)?;
+    contents
+        .lines()
+        .map(|l| {
+            l.char_indices()
+                .map(|(idx, c)| {
+                    c.to_digit(10).map(|d| d as u8).ok_or_else(|| {
+                        ReadError::parse::<u8>(&contents, &l[idx..idx + c.len_utf8()])
+                    })
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Reads the contents to an iterator of coordinates / char pairs
+pub fn read_grid_to_map<T: Input>(path: T) -> Vec<((usize, usize), char)> {
+    or_panic(try_read_grid_to_map(path))
+}
+
+/// Fallible version of [`read_grid_to_map`].
+#[allow(clippy::type_complexity)]
+pub fn try_read_grid_to_map<T: Input>(path: T) -> Result<Vec<((usize, usize), char)>, ReadError> {
+    Ok(try_contents(path)?
+        .lines()
+        .enumerate()
+        .flat_map(|(row, line)| {
+            line.chars()
+                .enumerate()
+                .map(|(col, ch)| ((row, col), ch))
+                .collect::<Vec<_>>()
+        })
+        .collect())
+}
+
+/// Reads the file to grids (vec of vec) of char records line delineated
+/// ```
+/// let input = "..##.
+/// .#...
+///
+/// ..#..
+/// ....#";
+/// let expected = vec![
+///     vec![
+///         vec!['.', '.', '#', '#', '.'],
+///         vec!['.', '#', '.', '.', '.']
+///         ],
+///     vec![
+///         vec!['.', '.', '#', '.', '.'],
+///         vec!['.', '.', '.', '.', '#']
+///         ]
+///     ];
+/// let actual = puzlib::read_grid_records(input);
+/// assert_eq!(expected, actual);
+/// ```
+pub fn read_grid_records<T: Input>(path: T) -> Vec<Vec<Vec<char>>> {
+    or_panic(try_read_grid_records(path))
+}
+
+/// Fallible version of [`read_grid_records`].
+pub fn try_read_grid_records<T: Input>(path: T) -> Result<Vec<Vec<Vec<char>>>, ReadError> {
+    Ok(records(&try_contents(path)?)
+        .map(|l| l.lines().map(|r| r.chars().collect()).collect())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_() {
+        use crate::measure::Vec2D;
+        let reader = FileReader::new("1.2\n34.");
+        let expected = vec![
+            (Vec2D(0, 0), 1),
+            (Vec2D(0, 2), 2),
+            (Vec2D(1, 0), 3),
+            (Vec2D(1, 1), 4),
+        ];
+        let actual = reader
+            .read_grid_convert(
+                &['.'],
+                |r| {
+                    let a: Vec2D<usize> = r.into();
+                    a.map(|u| u as i64)
+                },
+                |c| c as u8 - b'0',
+            )
+            .collect::<Vec<_>>();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_try_read_number_lists_error() {
+        let expected = ReadError::Parse {
+            line: 2,
+            column: 3,
+            text: "-4".into(),
+            target: "u32",
+        };
+        let actual = try_read_number_lists::<_, u32>("1 2\n3 -4 5\n", " ").unwrap_err();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_try_read_line_record() {
+        assert_eq!(Ok(vec![3, 4, 5]), try_read_line_record("3,4,5\n"));
+        let err = try_read_line_record::<_, i8>("3,4,500\n").unwrap_err();
+        assert_eq!(Some(1), err.line());
+    }
+
+    #[test]
+    fn test_try_read_grid_numbers() {
+        assert_eq!(
+            Ok(vec![vec![1, 2], vec![3, 4]]),
+            try_read_grid_numbers("12\n34")
+        );
+        let expected = ReadError::Parse {
+            line: 2,
+            column: 2,
+            text: "#".into(),
+            target: "u8",
+        };
+        assert_eq!(Err(expected), try_read_grid_numbers("12\n3#"));
+    }
+
+    #[test]
+    fn test_try_grid_with_ragged() {
+        let reader = FileReader::new("\n12\n34\n5\n");
+        assert_eq!(
+            Err(ReadError::RowLength {
+                line: 4,
+                expected: 2,
+                found: 1
+            }),
+            reader.try_grid_with(|c| c)
+        );
+        let grid = FileReader::new("12\n34\n").grid_with(|c| c.to_digit(10).unwrap());
+        assert_eq!(4, grid[crate::Vec2D(1, 1)]);
+    }
+
+    #[test]
+    fn test_explicit_sources() {
+        let literal = FileReader::from_str_literal("Cargo.toml");
+        assert_eq!("Cargo.toml", literal.contents());
+        let file = FileReader::from_path("Cargo.toml").unwrap();
+        assert!(file.contents().starts_with("[package]"));
+        assert!(FileReader::from_path("1,2,3").is_err());
+    }
+
+    #[test]
+    fn test_try_parse_lines_line_numbers() {
+        let reader = FileReader::new("a=1\n\na=2\nb=3\n");
+        let actual = reader
+            .try_parse_lines::<(u8,)>("a={}")
+            .map(|r| r.map_err(|e| e.line()))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![Ok((1,)), Ok((2,)), Err(Some(4))], actual);
+    }
+
+    #[test]
+    #[should_panic(expected = "could not read missing.txt")]
+    fn test_new_missing_path_panics() {
+        FileReader::new("missing.txt");
+    }
+
+    #[test]
+    #[should_panic(expected = "line 1, column 3: could not parse \"x\" as u8")]
+    fn test_read_number_lists_panics_with_position() {
+        read_number_lists::<_, u8>("1,x", ",");
+    }
+
+    const CRLF_NUMBERS: &str = "1\r\n2\r\n \r\n3\r\n4\r\n";
+    const MIXED_NUMBERS: &str = "1\n2\r\n\t\n3\r\n4";
+    const CRLF_GRID: &str = "#.\r\n.#\r\n\r\n..\r\n##\r\n";
+    const MIXED_GRID: &str = "#.\n.#\r\n\n..\n##";
+
+    #[test]
+    fn test_normalize() {
+        assert!(matches!(normalize("1\n\n2\n"), Cow::Borrowed(_)));
+        assert!(matches!(normalize("a\rb\n  \n"), Cow::Borrowed(_)));
+        assert_eq!("a\rb\n  \nc", normalize("a\rb\r\n  \r\nc"));
+        assert_eq!(" a \n", normalize(" a \r\n"));
+    }
+
+    #[test]
+    fn test_line_endings_lines() {
+        for input in [CRLF_NUMBERS, MIXED_NUMBERS] {
+            assert_eq!(vec!["1", "2", "3", "4"], read_lines(input));
+            assert_eq!(vec![1, 2, 3, 4], read_numbers::<_, u8>(input));
+            assert_eq!(vec!['1', '2', '3', '4'], read_line(input));
+            assert_eq!(
+                vec![vec![1, 2], vec![3, 4]],
+                read_number_records::<_, u8>(input)
+            );
+            let records = read_string_records(input);
+            assert_eq!(
+                vec!["1\n2", "3\n4"],
+                records.iter().map(|r| r.trim_end()).collect::<Vec<_>>()
+            );
+            let reader = FileReader::new(input);
+            assert_eq!(vec!["1", "2", "3", "4"], reader.lines().collect::<Vec<_>>());
+            assert_eq!("1234", reader.chars().collect::<String>());
+            assert_eq!(
+                vec![vec![1, 2], vec![3, 4]],
+                reader.records::<u8>().collect::<Vec<_>>()
+            );
+            assert_eq!(10, reader.ints::<u8>().sum::<u8>());
+            assert_eq!(2, reader.sections().count());
+            assert_eq!(
+                vec![(1,), (2,), (3,), (4,)],
+                reader.parse_lines::<(u8,)>("{}").collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_line_endings_separated() {
+        for input in ["1 2\r\n3 4\r\n", "1 2\n3 4\r\n \n"] {
+            assert_eq!(
+                vec![vec![1, 2], vec![3, 4]],
+                read_number_lists::<_, u8>(input, " ")
+            );
+        }
+        for input in ["1,2,3\r\n", "1,2,3\r\n\t\r\n"] {
+            assert_eq!(vec![1, 2, 3], read_line_record::<_, u8>(input));
+            assert_eq!(vec!["1", "2", "3"], read_line_sep(input, ","));
+            let reader = FileReader::new(input);
+            assert_eq!(
+                vec!["1", "2", "3"],
+                reader.line_sep(&",").collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn test_line_endings_grids() {
+        for input in [CRLF_GRID, MIXED_GRID] {
+            let expected = vec![
+                vec![vec!['#', '.'], vec!['.', '#']],
+                vec![vec!['.', '.'], vec!['#', '#']],
+            ];
+            assert_eq!(expected, read_grid_records(input));
+            let grid = read_grid(input);
+            assert_eq!(vec!['#', '.'], grid[0]);
+            assert_eq!(vec!['#', '#'], grid[4]);
+            assert!(grid[2].is_empty());
+            let map = read_grid_to_map(input);
+            assert_eq!(8, map.len());
+            assert!(map.iter().all(|(_, c)| *c != '\r'));
+            let reader = FileReader::new(input);
+            assert_eq!(4, reader.read_grid(&['.'], |p| p).count());
+            assert_eq!(
+                4,
+                reader
+                    .read_grid_convert(&['#'], |p| p, |c| c == '.')
+                    .count()
+            );
+        }
+        for input in ["12\r\n34\r\n", "12\n34\r\n"] {
+            assert_eq!(vec![vec![1, 2], vec![3, 4]], read_grid_numbers(input));
+        }
+    }
+
+    #[test]
+    fn test_raw_mode() {
+        assert_eq!("1\r\n \r\n2", raw_contents("1\r\n \r\n2"));
+        let reader = FileReader::from_source_raw(InputSource::Literal("a\r\n".into())).unwrap();
+        assert_eq!("a\r\n", reader.contents());
+        assert_eq!(vec!['a', '\r'], reader.chars().collect::<Vec<_>>());
+        assert_eq!("1\r\n2", contents(Raw("1\r\n2")));
+        assert_eq!(vec!["1\r\n2", "3"], read_string_records(Raw("1\r\n2\n\n3")));
+    }
+
+    #[test]
+    fn test_whitespace_grid_rows() {
+        let input = "#.#\n   \n#.#\n";
+        let grid = FileReader::new(input).grid();
+        assert_eq!((3, 3), (grid.height(), grid.width()));
+        assert_eq!(Some(&' '), grid.get(crate::Vec2D(1, 1)));
+        assert_eq!(vec![' '; 3], read_grid(input)[1]);
+        assert_eq!(3, FileReader::new(input).byte_grid().height());
+        let grid = FileReader::new("ab\ncd\n  \n").grid();
+        assert_eq!((2, 2), (grid.height(), grid.width()));
+        assert_eq!(2, read_grid("\n \nab\ncd\n\t\n").len());
+        assert_eq!(2, FileReader::new("ab\ncd\n  \n").byte_grid().height());
+        let expected = vec![
+            vec![vec!['#', '.'], vec!['.', '#']],
+            vec![vec!['.', '.'], vec!['#', '#']],
+        ];
+        assert_eq!(expected, read_grid_records("#.\n.#\n \n..\n##\n"));
+        let reader = FileReader::new("#.\n.#\n \n..\n##\n");
+        assert_eq!(2, reader.byte_grid_records().count());
+    }
+}
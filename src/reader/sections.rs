@@ -0,0 +1,123 @@
+use std::str::FromStr;
+
+use crate::Grid;
+
+use super::{FileReader, ReadError, is_blank, or_panic};
+
+/// Types that can be built from one blank-line separated section of the input.
+pub trait FromSection: Sized {
+    fn from_section(section: &FileReader) -> Result<Self, ReadError>;
+}
+
+impl FromSection for String {
+    /// The section text as is.
+    fn from_section(section: &FileReader) -> Result<Self, ReadError> {
+        Ok(section.contents().to_string())
+    }
+}
+
+impl<T: FromStr> FromSection for Vec<T> {
+    /// Each line of the section converted to T.
+    fn from_section(section: &FileReader) -> Result<Self, ReadError> {
+        section.lines().map(|l| section.parse(l)).collect()
+    }
+}
+
+impl FromSection for Grid<char> {
+    /// The section as a grid of chars.
+    fn from_section(section: &FileReader) -> Result<Self, ReadError> {
+        section.try_grid_with(|c| c)
+    }
+}
+
+impl FileReader {
+    /// Split the contents on blank lines into sub-readers. Errors from a section
+    /// report line numbers of the full input.
+    /// ```
+    /// let reader = puzlib::FileReader::new("47|53\n97|13\n\n75,47,61\n97,61\n");
+    /// let sections = reader.sections().collect::<Vec<_>>();
+    /// let rules = sections[0].parse_lines::<(u8, u8)>("{}|{}").collect::<Vec<_>>();
+    /// let updates = sections[1].ints_per_line::<u8>().collect::<Vec<_>>();
+    /// assert_eq!(vec![(47, 53), (97, 13)], rules);
+    /// assert_eq!(vec![vec![75, 47, 61], vec![97, 61]], updates);
+    /// ```
+    pub fn sections(&self) -> impl Iterator<Item = FileReader> {
+        let mut lines = self.contents.lines().enumerate().peekable();
+        std::iter::from_fn(move || {
+            while lines.next_if(|(_, l)| is_blank(l)).is_some() {}
+            let (first, line) = lines.next()?;
+            let mut contents = line.to_string();
+            while let Some((_, line)) = lines.next_if(|(_, l)| !is_blank(l)) {
+                contents.push('\n');
+                contents.push_str(line);
+            }
+            Some(FileReader {
+                contents,
+                line_offset: self.line_offset + first,
+            })
+        })
+    }
+
+    /// Parse the first section as A and the second as B. Any further sections are ignored.
+    /// ```
+    /// let reader = puzlib::FileReader::new("seeds: 79 14\n\n50 98 2\n52 50 48\n");
+    /// let (seeds, maps): (String, Vec<String>) = reader.split_sections();
+    /// assert_eq!("seeds: 79 14", seeds);
+    /// assert_eq!(2, maps.len());
+    /// ```
+    pub fn split_sections<A: FromSection, B: FromSection>(&self) -> (A, B) {
+        or_panic(self.try_split_sections())
+    }
+
+    /// Fallible version of [`FileReader::split_sections`].
+    pub fn try_split_sections<A: FromSection, B: FromSection>(&self) -> Result<(A, B), ReadError> {
+        let mut sections = self.sections();
+        let missing = |found| ReadError::MissingSection { expected: 2, found };
+        let first = sections.next().ok_or(missing(0))?;
+        let second = sections.next().ok_or(missing(1))?;
+        Ok((A::from_section(&first)?, B::from_section(&second)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sections_skip_extra_blank_lines() {
+        let reader = FileReader::new("\n1\n2\n\n\n\n3\n");
+        let actual = reader
+            .sections()
+            .map(|s| s.contents().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(vec!["1\n2", "3"], actual);
+    }
+
+    #[test]
+    fn test_split_sections_error_lines() {
+        let reader = FileReader::new("a\nb\n\n1\nx\n");
+        let actual = reader.try_split_sections::<String, Vec<u8>>().unwrap_err();
+        assert_eq!(Some(5), actual.line());
+    }
+
+    #[test]
+    fn test_split_sections_grid() {
+        let reader = FileReader::new("#.\n.#\n\n<>^\n");
+        let (grid, moves): (Grid<char>, String) = reader.split_sections();
+        assert_eq!(2, grid.height());
+        assert_eq!("<>^", moves);
+    }
+
+    #[test]
+    fn test_split_sections_missing() {
+        let reader = FileReader::new("1\n2\n");
+        let actual = reader.try_split_sections::<Vec<u8>, Vec<u8>>();
+        assert_eq!(
+            Err(ReadError::MissingSection {
+                expected: 2,
+                found: 1
+            }),
+            actual
+        );
+    }
+}
@@ -0,0 +1,151 @@
+use std::{
+    fmt::Display,
+    fs::read_to_string,
+    io::{ErrorKind, read_to_string as read_all, stdin},
+    path::{Path, PathBuf},
+};
+
+use super::{ReadError, normalize_owned};
+
+/// Where the reader input comes from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// A file on disk.
+    File(PathBuf),
+    /// Text supplied directly as the input.
+    Literal(String),
+    /// Everything readable from standard input.
+    Stdin,
+    /// Text compiled into the binary, usually with `include_str!`.
+    Embedded(&'static str),
+}
+
+impl InputSource {
+    /// Decide the source of an argument that may be a path or the input itself.
+    ///
+    /// Existing paths are files. Anything that looks like a path (a single line with
+    /// no whitespace that ends in a file extension, starts with `./` or `../`, or is
+    /// absolute) but does not exist is an error, everything else is literal input.
+    /// ```
+    /// use puzlib::InputSource;
+    /// assert_eq!(Ok(InputSource::Literal("1,2,3".into())), InputSource::infer("1,2,3"));
+    /// assert_eq!(Ok(InputSource::Literal("s1,x3/4".into())), InputSource::infer("s1,x3/4"));
+    /// assert!(InputSource::infer("inputs/day01.txt").is_err());
+    /// assert!(InputSource::infer("./inputs/day01").is_err());
+    /// ```
+    pub fn infer<S: AsRef<Path> + Display>(arg: S) -> Result<Self, ReadError> {
+        let path = arg.as_ref();
+        if path.exists() {
+            return Ok(Self::File(path.to_path_buf()));
+        }
+        let text = arg.to_string();
+        if looks_like_path(&text) {
+            return Err(ReadError::Io {
+                path: path.to_path_buf(),
+                kind: ErrorKind::NotFound,
+                message: "file does not exist (use FileReader::from_str_literal to read it as input text)"
+                    .into(),
+            });
+        }
+        Ok(Self::Literal(text))
+    }
+
+    /// Read the whole input to a string.
+    pub fn read(self) -> Result<String, ReadError> {
+        match self {
+            Self::File(path) => read_to_string(&path).map_err(|e| ReadError::io(path, e)),
+            Self::Literal(text) => Ok(text),
+            Self::Stdin => read_all(stdin()).map_err(|e| ReadError::io("<stdin>", e)),
+            Self::Embedded(text) => Ok(text.to_string()),
+        }
+    }
+}
+
+/// Input accepted by the free `read_*` functions: a path or the input text itself
+/// (see [`InputSource::infer`]), with line endings [normalized](super::normalize),
+/// or either one wrapped in [`Raw`] to keep the text exactly as read.
+pub trait Input {
+    fn read_input(self) -> Result<String, ReadError>;
+}
+
+impl<T: AsRef<Path> + Display> Input for T {
+    fn read_input(self) -> Result<String, ReadError> {
+        InputSource::infer(self)?.read().map(normalize_owned)
+    }
+}
+
+/// Read a path or input text without normalizing it.
+/// ```
+/// use puzlib::{Raw, read_line};
+/// assert_eq!(vec!['a', 'b'], read_line("ab\r\n"));
+/// assert_eq!(vec!['a', 'b', '\r'], read_line(Raw("ab\r\n")));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Raw<T>(pub T);
+
+impl<T: AsRef<Path> + Display> Input for Raw<T> {
+    fn read_input(self) -> Result<String, ReadError> {
+        InputSource::infer(self.0)?.read()
+    }
+}
+
+fn looks_like_path(text: &str) -> bool {
+    if text.is_empty() || text.chars().any(char::is_whitespace) {
+        return false;
+    }
+    let has_extension = Path::new(text)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| !ext.is_empty() && ext.chars().all(|c| c.is_ascii_alphabetic()));
+    let relative = ["./", "../", ".\\", "..\\"]
+        .iter()
+        .any(|prefix| text.starts_with(prefix));
+    relative || Path::new(text).has_root() || has_extension
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_looks_like_path() {
+        assert!(looks_like_path("input.txt"));
+        assert!(looks_like_path("inputs/day01.txt"));
+        assert!(looks_like_path("./inputs/day01"));
+        assert!(looks_like_path("../day01"));
+        assert!(looks_like_path("/tmp/day01"));
+        assert!(!looks_like_path("inputs/day01"));
+        assert!(!looks_like_path("s1,x3/4,pe/b"));
+        assert!(!looks_like_path("1/2"));
+        assert!(!looks_like_path("1.5"));
+        assert!(!looks_like_path("3,4,5"));
+        assert!(!looks_like_path("x=1, y=2"));
+        assert!(!looks_like_path("1234\n5678"));
+    }
+
+    #[test]
+    fn test_slashes_in_literal_input() {
+        for input in ["s1,x3/4,pe/b", "1/2"] {
+            let reader = crate::FileReader::try_new(input).unwrap();
+            assert_eq!(input, reader.contents());
+        }
+    }
+
+    #[test]
+    fn test_infer_missing_path() {
+        let err = InputSource::infer("does_not_exist.txt").unwrap_err();
+        assert!(matches!(
+            err,
+            ReadError::Io {
+                kind: ErrorKind::NotFound,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_infer_existing_path() {
+        let expected = InputSource::File(PathBuf::from("Cargo.toml"));
+        assert_eq!(Ok(expected), InputSource::infer("Cargo.toml"));
+    }
+}
@@ -0,0 +1,214 @@
+use std::{
+    fs::File,
+    io::{BufRead, BufReader, StdinLock, stdin},
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::Integer;
+
+use super::{Minus, ReadError, error::parse_in, ints::int_tokens, or_panic};
+
+/// Lines read one at a time from any buffered reader, for inputs too large to hold in memory.
+///
+/// Offers the same `lines`/`records`/`ints` methods as [`FileReader`](super::FileReader)
+/// with owned items, so solver code written against `impl Iterator<Item = impl AsRef<str>>`
+/// works with either. Line endings and blank lines are handled the same way as `FileReader`.
+/// ```
+/// let stream = puzlib::LineStream::from_str_literal("1 2\r\n\r\n3 4\n");
+/// assert_eq!(10, stream.ints::<i32>().sum());
+/// ```
+pub struct LineStream<R> {
+    reader: R,
+    name: PathBuf,
+}
+
+impl LineStream<BufReader<File>> {
+    /// Stream the lines of a file.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, ReadError> {
+        let file = File::open(&path).map_err(|e| ReadError::io(&path, e))?;
+        Ok(Self {
+            reader: BufReader::new(file),
+            name: path.as_ref().to_path_buf(),
+        })
+    }
+}
+
+impl LineStream<StdinLock<'static>> {
+    /// Stream the lines of standard input.
+    pub fn from_stdin() -> Self {
+        Self {
+            reader: stdin().lock(),
+            name: "<stdin>".into(),
+        }
+    }
+}
+
+impl<'a> LineStream<&'a [u8]> {
+    /// Stream the lines of some text already in memory.
+    pub fn from_str_literal(text: &'a str) -> Self {
+        Self::new(text.as_bytes())
+    }
+}
+
+impl<R: BufRead> LineStream<R> {
+    /// Stream the lines of any buffered reader.
+    pub fn new(reader: R) -> Self {
+        Self {
+            reader,
+            name: "<reader>".into(),
+        }
+    }
+
+    /// Every line with its 1-based line number, including blank ones.
+    fn numbered_lines(self) -> impl Iterator<Item = Result<(usize, String), ReadError>> {
+        let Self { mut reader, name } = self;
+        let mut line_no = 0;
+        let mut failed = false;
+        std::iter::from_fn(move || {
+            if failed {
+                return None;
+            }
+            let mut line = String::new();
+            match reader.read_line(&mut line) {
+                Ok(0) => None,
+                Ok(_) => {
+                    line_no += 1;
+                    let trimmed = line.trim_end_matches(['\n', '\r']);
+                    let len = if trimmed.trim().is_empty() {
+                        0
+                    } else {
+                        trimmed.len()
+                    };
+                    line.truncate(len);
+                    Some(Ok((line_no, line)))
+                }
+                Err(e) => {
+                    failed = true;
+                    Some(Err(ReadError::io(&name, e)))
+                }
+            }
+        })
+    }
+
+    /// Non-blank lines.
+    pub fn lines(self) -> impl Iterator<Item = String> {
+        self.try_lines().map(or_panic)
+    }
+
+    /// Fallible version of [`LineStream::lines`]. Fails on I/O errors.
+    pub fn try_lines(self) -> impl Iterator<Item = Result<String, ReadError>> {
+        self.numbered_lines()
+            .filter(|l| !matches!(l, Ok((_, l)) if l.is_empty()))
+            .map(|l| l.map(|(_, l)| l))
+    }
+
+    /// Blank-line separated records where each line is converted to type N.
+    pub fn records<N: FromStr>(self) -> impl Iterator<Item = Vec<N>> {
+        self.try_records().map(or_panic)
+    }
+
+    /// Fallible version of [`LineStream::records`].
+    pub fn try_records<N: FromStr>(self) -> impl Iterator<Item = Result<Vec<N>, ReadError>> {
+        let mut lines = self.numbered_lines().peekable();
+        std::iter::from_fn(move || {
+            while lines
+                .next_if(|l| matches!(l, Ok((_, l)) if l.is_empty()))
+                .is_some()
+            {}
+            lines.peek()?;
+            let mut record = Ok(Vec::new());
+            while let Some(next) = lines.next_if(|l| !matches!(l, Ok((_, l)) if l.is_empty())) {
+                let parsed = next.and_then(|(line_no, line)| parse_line::<N>(&line, line_no));
+                if let Ok(values) = &mut record {
+                    match parsed {
+                        Ok(value) => values.push(value),
+                        Err(e) => record = Err(e),
+                    }
+                }
+            }
+            Some(record)
+        })
+    }
+
+    /// Every integer in the input, ignoring any surrounding text.
+    pub fn ints<T: Integer>(self) -> impl Iterator<Item = T> {
+        self.ints_per_line().flatten()
+    }
+
+    /// The integers of each non-blank line.
+    pub fn ints_per_line<T: Integer>(self) -> impl Iterator<Item = Vec<T>> {
+        self.ints_per_line_with(Minus::Auto)
+    }
+
+    /// The integers of each non-blank line with explicit handling of `-`.
+    pub fn ints_per_line_with<T: Integer>(self, minus: Minus) -> impl Iterator<Item = Vec<T>> {
+        self.try_ints_per_line_with(minus).map(or_panic)
+    }
+
+    /// Fallible version of [`LineStream::ints_per_line_with`].
+    pub fn try_ints_per_line_with<T: Integer>(
+        self,
+        minus: Minus,
+    ) -> impl Iterator<Item = Result<Vec<T>, ReadError>> {
+        self.numbered_lines()
+            .filter(|l| !matches!(l, Ok((_, l)) if l.is_empty()))
+            .map(move |l| {
+                let (line_no, line) = l?;
+                int_tokens(&line, minus, T::SIGNED)
+                    .map(|token| parse_in(&line, token).map_err(|e| e.offset_lines(line_no - 1)))
+                    .collect()
+            })
+    }
+}
+
+/// Parse a whole line, reporting errors against line `line_no`.
+fn parse_line<N: FromStr>(line: &str, line_no: usize) -> Result<N, ReadError> {
+    parse_in(line, line).map_err(|e| e.offset_lines(line_no - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_lines() {
+        let stream = LineStream::from_str_literal("a\r\n \r\nb\n\nc");
+        assert_eq!(vec!["a", "b", "c"], stream.lines().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_stream_records() {
+        let stream = LineStream::from_str_literal("1\n2\n\n\nx\n3\n\n4\n");
+        let actual = stream
+            .try_records::<u8>()
+            .map(|r| r.map_err(|e| e.line()))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![Ok(vec![1, 2]), Err(Some(5)), Ok(vec![4])], actual);
+    }
+
+    #[test]
+    fn test_stream_ints_error_position() {
+        let stream = LineStream::from_str_literal("1 2\n3 999\n");
+        let actual = stream
+            .try_ints_per_line_with::<u8>(Minus::Auto)
+            .collect::<Vec<_>>();
+        assert_eq!(Ok(vec![1, 2]), actual[0]);
+        assert_eq!(
+            Err(ReadError::Parse {
+                line: 2,
+                column: 3,
+                text: "999".into(),
+                target: "u8"
+            }),
+            actual[1]
+        );
+    }
+
+    #[test]
+    fn test_stream_from_path() {
+        let stream = LineStream::from_path("Cargo.toml").unwrap();
+        assert_eq!(Some("[package]".to_string()), stream.lines().next());
+        assert!(LineStream::from_path("missing.txt").is_err());
+    }
+}
@@ -0,0 +1,277 @@
+use std::{any::type_name, str::FromStr};
+
+use super::ReadError;
+
+/// A line pattern like `"Sensor at x={}, y={}"` where each `{}` captures a field.
+/// Use `{{` and `}}` for literal braces.
+///
+/// A field extends up to the first occurrence of the literal text following it,
+/// or to the end of the line for a trailing field.
+/// ```
+/// use puzlib::Template;
+/// let template = Template::new("x={}, y={}");
+/// let (x, y): (i32, i32) = template.parse("x=-2, y=15").unwrap();
+/// assert_eq!((-2, 15), (x, y));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Part {
+    Literal(String),
+    Field,
+}
+
+/// A piece of a line captured by a `{}` in a [`Template`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Field<'a> {
+    pub text: &'a str,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column of the first character.
+    pub column: usize,
+}
+
+impl Field<'_> {
+    /// Convert the field to type T.
+    pub fn parse<T: FromStr>(&self) -> Result<T, ReadError> {
+        self.text.parse().map_err(|_| ReadError::Parse {
+            line: self.line,
+            column: self.column,
+            text: self.text.to_string(),
+            target: type_name::<T>(),
+        })
+    }
+}
+
+/// Types that can be built from the fields captured by a [`Template`].
+/// Implemented for tuples of up to eight [`FromStr`] types and for `Vec<T>`.
+/// ```
+/// use puzlib::{Field, FromFields, ReadError, Template};
+/// struct Sensor {
+///     x: i64,
+///     y: i64,
+/// }
+/// impl FromFields for Sensor {
+///     fn from_fields(fields: &[Field<'_>]) -> Result<Self, ReadError> {
+///         Ok(Self { x: fields[0].parse()?, y: fields[1].parse()? })
+///     }
+/// }
+/// let sensor: Sensor = Template::new("Sensor at x={}, y={}").parse("Sensor at x=2, y=18").unwrap();
+/// assert_eq!((2, 18), (sensor.x, sensor.y));
+/// ```
+pub trait FromFields: Sized {
+    fn from_fields(fields: &[Field<'_>]) -> Result<Self, ReadError>;
+}
+
+impl Template {
+    /// Compile a template. Panics if two fields are adjacent since
+    /// there would be no way to tell where the first one ends.
+    pub fn new(pattern: &str) -> Self {
+        let mut parts = Vec::new();
+        let mut literal = String::new();
+        let mut chars = pattern.chars().peekable();
+        while let Some(ch) = chars.next() {
+            match (ch, chars.peek()) {
+                ('{', Some('{')) | ('}', Some('}')) => {
+                    chars.next();
+                    literal.push(ch);
+                }
+                ('{', Some('}')) => {
+                    chars.next();
+                    if literal.is_empty() && parts.last() == Some(&Part::Field) {
+                        panic!("Template {pattern:?} has adjacent fields");
+                    }
+                    if !literal.is_empty() {
+                        parts.push(Part::Literal(std::mem::take(&mut literal)));
+                    }
+                    parts.push(Part::Field);
+                }
+                _ => literal.push(ch),
+            }
+        }
+        if !literal.is_empty() {
+            parts.push(Part::Literal(literal));
+        }
+        Self { parts }
+    }
+
+    /// Number of fields in the template.
+    pub fn field_count(&self) -> usize {
+        self.parts.iter().filter(|p| **p == Part::Field).count()
+    }
+
+    /// Split a line into its fields. `line_no` is only used for error reporting.
+    pub fn captures<'a>(&self, line: &'a str, line_no: usize) -> Result<Vec<Field<'a>>, ReadError> {
+        let column = |pos: usize| line[..pos].chars().count() + 1;
+        let mut fields = Vec::new();
+        let mut pos = 0;
+        for (idx, part) in self.parts.iter().enumerate() {
+            match part {
+                Part::Literal(lit) => {
+                    let rest = &line[pos..];
+                    if !rest.starts_with(lit.as_str()) {
+                        let offset = rest
+                            .char_indices()
+                            .zip(lit.chars())
+                            .find(|((_, a), b)| a != b)
+                            .map(|((i, _), _)| i)
+                            .unwrap_or(rest.len());
+                        return Err(ReadError::Mismatch {
+                            line: line_no,
+                            column: column(pos + offset),
+                            expected: lit.clone(),
+                            found: rest[offset..].to_string(),
+                        });
+                    }
+                    pos += lit.len();
+                }
+                Part::Field => {
+                    let end = match self.parts.get(idx + 1) {
+                        Some(Part::Literal(lit)) => match line[pos..].find(lit.as_str()) {
+                            Some(len) => pos + len,
+                            None => {
+                                return Err(ReadError::Mismatch {
+                                    line: line_no,
+                                    column: column(line.len()),
+                                    expected: lit.clone(),
+                                    found: String::new(),
+                                });
+                            }
+                        },
+                        _ => line.len(),
+                    };
+                    fields.push(Field {
+                        text: &line[pos..end],
+                        line: line_no,
+                        column: column(pos),
+                    });
+                    pos = end;
+                }
+            }
+        }
+        if pos < line.len() {
+            return Err(ReadError::Mismatch {
+                line: line_no,
+                column: column(pos),
+                expected: String::new(),
+                found: line[pos..].to_string(),
+            });
+        }
+        Ok(fields)
+    }
+
+    /// Parse a single line into T.
+    pub fn parse<T: FromFields>(&self, line: &str) -> Result<T, ReadError> {
+        self.parse_line(line, 1)
+    }
+
+    /// Parse a single line into T, reporting errors against line `line_no`.
+    pub fn parse_line<T: FromFields>(&self, line: &str, line_no: usize) -> Result<T, ReadError> {
+        T::from_fields(&self.captures(line, line_no)?)
+    }
+}
+
+impl<T: FromStr> FromFields for Vec<T> {
+    fn from_fields(fields: &[Field<'_>]) -> Result<Self, ReadError> {
+        fields.iter().map(|f| f.parse()).collect()
+    }
+}
+
+macro_rules! tuple_from_fields {
+    ($count:expr; $($t:ident $idx:tt),+) => {
+        impl<$($t: FromStr),+> FromFields for ($($t,)+) {
+            fn from_fields(fields: &[Field<'_>]) -> Result<Self, ReadError> {
+                if fields.len() != $count {
+                    return Err(ReadError::FieldCount {
+                        line: fields.first().map(|f| f.line).unwrap_or(0),
+                        expected: $count,
+                        found: fields.len(),
+                    });
+                }
+                Ok(($(fields[$idx].parse::<$t>()?,)+))
+            }
+        }
+    };
+}
+
+tuple_from_fields!(1; A 0);
+tuple_from_fields!(2; A 0, B 1);
+tuple_from_fields!(3; A 0, B 1, C 2);
+tuple_from_fields!(4; A 0, B 1, C 2, D 3);
+tuple_from_fields!(5; A 0, B 1, C 2, D 3, E 4);
+tuple_from_fields!(6; A 0, B 1, C 2, D 3, E 4, F 5);
+tuple_from_fields!(7; A 0, B 1, C 2, D 3, E 4, F 5, G 6);
+tuple_from_fields!(8; A 0, B 1, C 2, D 3, E 4, F 5, G 6, H 7);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SENSOR: &str = "Sensor at x={}, y={}: closest beacon is at x={}, y={}";
+
+    #[test]
+    fn test_parse_tuple() {
+        let expected = (2, 18, -2, 15);
+        let actual = Template::new(SENSOR)
+            .parse::<(i64, i64, i64, i64)>("Sensor at x=2, y=18: closest beacon is at x=-2, y=15")
+            .unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_mismatch_column() {
+        let expected = ReadError::Mismatch {
+            line: 1,
+            column: 8,
+            expected: "Sensor at x=".into(),
+            found: "on x=2, y=18".into(),
+        };
+        let actual = Template::new("Sensor at x={}, y={}")
+            .parse::<(i64, i64)>("Sensor on x=2, y=18")
+            .unwrap_err();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_field_parse_error() {
+        let expected = ReadError::Parse {
+            line: 4,
+            column: 13,
+            text: "abc".into(),
+            target: "u8",
+        };
+        let actual = Template::new("move {} from {} to {}")
+            .parse_line::<(u8, u8, u8)>("move 1 from abc to 3", 4)
+            .unwrap_err();
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_escaped_braces_and_vec() {
+        let template = Template::new("{{{}}} -> {}");
+        assert_eq!(2, template.field_count());
+        assert_eq!(Ok(vec![7, 9]), template.parse::<Vec<u32>>("{7} -> 9"));
+    }
+
+    #[test]
+    fn test_field_count() {
+        let actual = Template::new("{},{}").parse::<(u8,)>("1,2").unwrap_err();
+        assert_eq!(
+            ReadError::FieldCount {
+                line: 1,
+                expected: 1,
+                found: 2
+            },
+            actual
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "adjacent fields")]
+    fn test_adjacent_fields() {
+        Template::new("{}{}");
+    }
+}
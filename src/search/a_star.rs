@@ -1,80 +1,192 @@
 use std::{
-    cmp::Eq,
-    collections::{BinaryHeap, HashMap, HashSet},
+    collections::{BinaryHeap, HashMap},
     hash::Hash,
 };
 
-use super::{Weighted, get_path};
+use super::{
+    Cost, CostedPath, MinHeapState, Observer, SearchStats, ShortestPaths, Start, Tracker, Weighted,
+    walk_path,
+};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct MinHeapState<S: Hash + Ord + PartialOrd + Eq + PartialEq> {
-    node: S,
-    cost: usize,
+/// Cheapest path from the start to the first node where [`Graph::is_done`](super::Graph::is_done)
+/// holds, guided by a heuristic that never overestimates the remaining cost.
+pub fn a_star<
+    N: Hash + Ord + PartialOrd + Clone + std::fmt::Debug,
+    G: Weighted<Node = N>,
+    F: Fn(&N) -> G::Cost,
+>(
+    start: &N,
+    graph: &G,
+    heuristic: F,
+) -> Option<CostedPath<N, G::Cost>> {
+    a_star_observed(start, graph, heuristic, &mut ()).0
 }
 
-impl<S: Hash + Ord> Ord for MinHeapState<S> {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other
-            .cost
-            .cmp(&self.cost)
-            .then_with(|| self.node.cmp(&other.node))
-    }
+/// [`a_star`] reporting progress to an observer, with statistics about the search.
+/// Costs passed to the observer are path costs without the heuristic.
+pub fn a_star_observed<
+    N: Hash + Ord + PartialOrd + Clone + std::fmt::Debug,
+    G: Weighted<Node = N>,
+    F: Fn(&N) -> G::Cost,
+>(
+    start: &N,
+    graph: &G,
+    heuristic: F,
+    observer: &mut impl Observer<N, G::Cost>,
+) -> (Option<CostedPath<N, G::Cost>>, SearchStats) {
+    a_star_search([start.clone()], graph, heuristic, observer)
 }
 
-impl<S: Hash + Ord> PartialOrd for MinHeapState<S> {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
+/// [`a_star`] from any of several starts. Each start is a node or a `(node, initial cost)` pair.
+pub fn a_star_multi<
+    N: Hash + Ord + PartialOrd + Clone + std::fmt::Debug,
+    G: Weighted<Node = N>,
+    F: Fn(&N) -> G::Cost,
+>(
+    starts: impl IntoIterator<Item = impl Into<Start<N, G::Cost>>>,
+    graph: &G,
+    heuristic: F,
+) -> Option<CostedPath<N, G::Cost>> {
+    a_star_search(starts, graph, heuristic, &mut ()).0
 }
 
-pub fn a_star<
+fn a_star_search<
     N: Hash + Ord + PartialOrd + Clone + std::fmt::Debug,
     G: Weighted<Node = N>,
-    F: Fn(&N) -> usize,
+    F: Fn(&N) -> G::Cost,
 >(
-    start: &N,
+    starts: impl IntoIterator<Item = impl Into<Start<N, G::Cost>>>,
     graph: &G,
     heuristic: F,
-) -> Option<(HashMap<N, usize>, Vec<N>)> {
-    let mut heap: BinaryHeap<MinHeapState<N>> = BinaryHeap::new();
+    observer: &mut impl Observer<N, G::Cost>,
+) -> (Option<CostedPath<N, G::Cost>>, SearchStats) {
+    let mut stats = Tracker::new();
+    let mut heap: BinaryHeap<MinHeapState<N, G::Cost>> = BinaryHeap::new();
     let mut path: HashMap<N, N> = HashMap::new();
-    let mut dist: HashMap<N, usize> = HashMap::new();
-    let mut index: HashSet<N> = HashSet::new();
+    let mut dist: HashMap<N, G::Cost> = HashMap::new();
 
-    heap.push(MinHeapState {
-        node: start.clone(),
-        cost: heuristic(start),
-    });
-    dist.insert(start.clone(), 0);
-    index.insert(start.clone());
+    for Start { node, cost } in starts.into_iter().map(Into::into) {
+        if dist.get(&node).is_none_or(|known| cost < *known) {
+            heap.push(MinHeapState {
+                node: node.clone(),
+                cost: cost.plus(heuristic(&node)),
+            });
+            dist.insert(node, cost);
+        }
+    }
+    stats.frontier(heap.len());
 
-    while let Some(MinHeapState { node, .. }) = heap.pop() {
+    while let Some(MinHeapState { node, cost }) = heap.pop() {
+        let so_far = dist[&node];
+        // Already have a better path to node.
+        if cost > so_far.plus(heuristic(&node)) {
+            continue;
+        }
         // Reached our goal.
         if graph.is_done(&node) {
-            return Some((dist, get_path(path, node, start)));
+            let found = !observer.on_goal(&node, so_far).is_abort();
+            let path = walk_path(&path, node);
+            return (found.then_some((dist, path)), stats.finish());
         }
 
+        stats.expand();
+        if observer.on_expand(&node, so_far).is_abort() {
+            return (None, stats.finish());
+        }
         for next_move in graph.moves(&node) {
+            let tentative_cost = so_far.plus(graph.weight(&node, &next_move));
             // Build the queue as we go instead of putting all nodes in at the start.
-            if index.insert(next_move.clone()) {
-                dist.insert(next_move.clone(), usize::MAX);
-            }
-            let tentative_cost = dist[&node] + graph.weight(&node, &next_move);
-            if tentative_cost < dist[&next_move] {
-                let next_cost = tentative_cost + heuristic(&next_move);
+            if dist
+                .get(&next_move)
+                .is_none_or(|known| tentative_cost < *known)
+            {
+                if observer
+                    .on_relax(&node, &next_move, tentative_cost)
+                    .is_abort()
+                {
+                    return (None, stats.finish());
+                }
+                let next_cost = tentative_cost.plus(heuristic(&next_move));
                 heap.push(MinHeapState {
                     node: next_move.clone(),
                     cost: next_cost,
                 });
-                dist.entry(next_move.clone())
-                    .and_modify(|v| *v = tentative_cost);
-                let cur = path.entry(next_move.clone()).or_insert(node.clone());
-                *cur = node.clone();
+                dist.insert(next_move.clone(), tentative_cost);
+                path.insert(next_move, node.clone());
+            }
+        }
+        stats.frontier(heap.len());
+    }
+
+    (None, stats.finish())
+}
+
+/// A* that keeps every equal cost predecessor so all shortest paths to the goal
+/// can be recovered. The heuristic must be consistent (never drop by more than
+/// the step cost) for the result to hold every shortest path.
+pub fn a_star_all_paths<
+    N: Hash + Ord + PartialOrd + Clone,
+    G: Weighted<Node = N>,
+    F: Fn(&N) -> G::Cost,
+>(
+    start: &N,
+    graph: &G,
+    heuristic: F,
+) -> Option<ShortestPaths<N, G::Cost>> {
+    let mut heap: BinaryHeap<MinHeapState<N, G::Cost>> = BinaryHeap::new();
+    let mut dist: HashMap<N, G::Cost> = HashMap::new();
+    let mut preds: HashMap<N, Vec<N>> = HashMap::new();
+    let mut ends = Vec::new();
+    let mut best = None;
+
+    heap.push(MinHeapState {
+        node: start.clone(),
+        cost: heuristic(start),
+    });
+    dist.insert(start.clone(), G::Cost::zero());
+
+    while let Some(MinHeapState { node, cost }) = heap.pop() {
+        // Every path from here on is longer than the best.
+        if best.is_some_and(|best| cost > best) {
+            break;
+        }
+        let so_far = dist[&node];
+        // Already have a better path to node.
+        if cost > so_far.plus(heuristic(&node)) {
+            continue;
+        }
+        if graph.is_done(&node) {
+            best = Some(so_far);
+            ends.push(node);
+            continue;
+        }
+        for next_move in graph.moves(&node) {
+            let tentative_cost = so_far.plus(graph.weight(&node, &next_move));
+            match dist.get(&next_move) {
+                // The start has no predecessor, even at the end of a zero cost cycle.
+                Some(_) if next_move == *start => (),
+                Some(known) if tentative_cost == *known => {
+                    preds.entry(next_move).or_default().push(node.clone());
+                }
+                Some(known) if tentative_cost > *known => (),
+                _ => {
+                    heap.push(MinHeapState {
+                        node: next_move.clone(),
+                        cost: tentative_cost.plus(heuristic(&next_move)),
+                    });
+                    dist.insert(next_move.clone(), tentative_cost);
+                    preds.insert(next_move, vec![node.clone()]);
+                }
             }
         }
     }
 
-    None
+    best.map(|cost| ShortestPaths {
+        dist,
+        preds,
+        ends,
+        cost,
+    })
 }
 
 #[cfg(test)]
@@ -100,6 +212,54 @@ S50505E
         assert_eq!(expected, actual);
     }
 
+    #[test]
+    fn test_a_star_multiple_starts() {
+        let chamber: Chamber = r#"#####
+S000E
+#0#0#
+#S00#
+#####"#
+            .lines()
+            .collect::<Vec<_>>()
+            .into();
+        let (left, bottom) = (chamber.start[0], chamber.start[1]);
+        let (dist, path) = a_star_multi(chamber.start.iter().copied(), &chamber, |n| {
+            chamber.heuristic(n)
+        })
+        .unwrap();
+        assert_eq!(Some(&left), path.first());
+        assert_eq!(4, dist[&chamber.end]);
+        let (dist, path) =
+            a_star_multi([(left, 3), (bottom, 0)], &chamber, |n| chamber.heuristic(n)).unwrap();
+        assert_eq!(Some(&bottom), path.first());
+        assert_eq!(5, dist[&chamber.end]);
+    }
+
+    #[test]
+    fn test_a_star_all_paths() {
+        let chamber: Chamber = r#"#######
+#6769##
+S50505E
+#97434#
+#######"#
+            .lines()
+            .collect::<Vec<_>>()
+            .into();
+        let paths =
+            a_star_all_paths(&chamber.start[0], &chamber, |n| chamber.heuristic(n)).unwrap();
+        assert_eq!(28, paths.cost());
+        let expected = crate::dijkstra_all_paths(&chamber.start[0], &chamber).unwrap();
+        assert_eq!(expected.count(), paths.count());
+        assert_eq!(expected.nodes(), paths.nodes());
+        assert_eq!(paths.count(), paths.paths().len());
+        assert!(
+            paths
+                .paths()
+                .iter()
+                .all(|p| p.first() == chamber.start.first() && p.last() == Some(&chamber.end))
+        );
+    }
+
     #[derive(Debug, Default)]
     struct Chamber {
         chamber: HashMap<Vec2D<i64>, i64>,
@@ -144,6 +304,8 @@ S50505E
         }
     }
     impl Weighted for Chamber {
+        type Cost = usize;
+
         fn weight(&self, cur: &Self::Node, next: &Self::Node) -> usize {
             let a = self.chamber[cur];
             let b = self.chamber[next];
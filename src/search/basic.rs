@@ -1,40 +1,201 @@
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 
-use super::{Graph, get_path};
+use super::{Graph, Observer, SearchStats, Tracker, get_path, walk_path};
 
 pub fn dfs<G: Graph>(start: &G::Node, graph: &G) -> Option<Vec<G::Node>> {
+    dfs_observed(start, graph, &mut ()).0
+}
+
+/// [`dfs`] reporting progress to an observer, with statistics about the search.
+pub fn dfs_observed<G: Graph>(
+    start: &G::Node,
+    graph: &G,
+    observer: &mut impl Observer<G::Node, usize>,
+) -> (Option<Vec<G::Node>>, SearchStats) {
+    let mut stats = Tracker::new();
     let mut path = HashMap::new();
-    let mut to_visit = vec![start.clone()];
-    while let Some(node) = to_visit.pop() {
-        if path.contains_key(&node) {
+    let mut visited = HashSet::new();
+    let mut to_visit = vec![(start.clone(), None, 0)];
+    while let Some((node, parent, steps)) = to_visit.pop() {
+        // The predecessor is only known once a node is visited, not when it is pushed.
+        if !visited.insert(node.clone()) {
             continue;
         }
+        if let Some(parent) = parent {
+            path.insert(node.clone(), parent);
+        }
         if graph.is_done(&node) {
-            return Some(get_path(path, node, start));
+            let found = !observer.on_goal(&node, steps).is_abort();
+            return (found.then(|| walk_path(&path, node)), stats.finish());
         }
-        for next_move in graph.moves(&node) {
-            to_visit.push(next_move.clone());
-            path.insert(next_move.clone(), node.clone());
+        stats.expand();
+        if observer.on_expand(&node, steps).is_abort() {
+            return (None, stats.finish());
         }
+        let mut moves = graph.moves(&node);
+        // Visit moves in the order given.
+        moves.reverse();
+        for next_move in moves {
+            if visited.contains(&next_move) {
+                continue;
+            }
+            if observer.on_relax(&node, &next_move, steps + 1).is_abort() {
+                return (None, stats.finish());
+            }
+            to_visit.push((next_move, Some(node.clone()), steps + 1));
+        }
+        stats.frontier(to_visit.len());
     }
-    None
+    (None, stats.finish())
 }
 
 pub fn bfs<G: Graph>(start: &G::Node, graph: &G) -> Option<Vec<G::Node>> {
+    bfs_observed(start, graph, &mut ()).0
+}
+
+/// [`bfs`] reporting progress to an observer, with statistics about the search.
+pub fn bfs_observed<G: Graph>(
+    start: &G::Node,
+    graph: &G,
+    observer: &mut impl Observer<G::Node, usize>,
+) -> (Option<Vec<G::Node>>, SearchStats) {
+    let mut stats = Tracker::new();
     let mut path = HashMap::new();
     let mut to_visit = VecDeque::new();
-    to_visit.push_front(start.clone());
-    while let Some(node) = to_visit.pop_front() {
+    to_visit.push_front((start.clone(), 0));
+    while let Some((node, steps)) = to_visit.pop_front() {
         if graph.is_done(&node) {
-            return Some(get_path(path, node, start));
+            let found = !observer.on_goal(&node, steps).is_abort();
+            return (found.then(|| get_path(path, node, start)), stats.finish());
+        }
+        stats.expand();
+        if observer.on_expand(&node, steps).is_abort() {
+            return (None, stats.finish());
         }
         for next_move in graph.moves(&node) {
             if path.contains_key(&next_move) {
                 continue;
             }
-            to_visit.push_back(next_move.clone());
+            if observer.on_relax(&node, &next_move, steps + 1).is_abort() {
+                return (None, stats.finish());
+            }
+            to_visit.push_back((next_move.clone(), steps + 1));
             path.insert(next_move.clone(), node.clone());
         }
+        stats.frontier(to_visit.len());
+    }
+    (None, stats.finish())
+}
+
+/// Number of steps from `start` to every reachable node.
+pub fn bfs_distances<G: Graph>(start: &G::Node, graph: &G) -> HashMap<G::Node, usize> {
+    multi_source_bfs([start.clone()], graph)
+}
+
+/// Number of steps to every reachable node from whichever start is closest.
+/// Ignores [`Graph::is_done`].
+/// ```
+/// use puzlib::{FileReader, Vec2D, multi_source_bfs};
+/// let grid = FileReader::new("0...\n.##.\n...0\n").grid();
+/// let graph = grid.graph(|c| *c != '#');
+/// let dist = multi_source_bfs(grid.positions_of(&'0'), &graph);
+/// assert_eq!(2, dist[&Vec2D(0, 2)]);
+/// assert_eq!(10, dist.len());
+/// ```
+pub fn multi_source_bfs<G: Graph>(
+    starts: impl IntoIterator<Item = G::Node>,
+    graph: &G,
+) -> HashMap<G::Node, usize> {
+    multi_source_bfs_limited(starts, graph, usize::MAX)
+}
+
+/// [`multi_source_bfs`] that stops expanding at `max_depth` steps.
+/// Nodes further than `max_depth` are left out.
+pub fn multi_source_bfs_limited<G: Graph>(
+    starts: impl IntoIterator<Item = G::Node>,
+    graph: &G,
+    max_depth: usize,
+) -> HashMap<G::Node, usize> {
+    let mut dist = HashMap::new();
+    let mut to_visit = VecDeque::new();
+    for start in starts {
+        if !dist.contains_key(&start) {
+            dist.insert(start.clone(), 0);
+            to_visit.push_back(start);
+        }
+    }
+    while let Some(node) = to_visit.pop_front() {
+        let steps = dist[&node];
+        if steps >= max_depth {
+            continue;
+        }
+        for next_move in graph.moves(&node) {
+            if dist.contains_key(&next_move) {
+                continue;
+            }
+            dist.insert(next_move.clone(), steps + 1);
+            to_visit.push_back(next_move);
+        }
+    }
+    dist
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Line(usize);
+
+    impl Graph for Line {
+        type Node = usize;
+
+        fn height(&self) -> usize {
+            1
+        }
+
+        fn width(&self) -> usize {
+            self.0
+        }
+
+        fn moves(&self, node: &Self::Node) -> Vec<Self::Node> {
+            [node.checked_sub(1), Some(node + 1)]
+                .into_iter()
+                .flatten()
+                .filter(|n| *n < self.0)
+                .collect()
+        }
+
+        fn is_done(&self, node: &Self::Node) -> bool {
+            *node == self.0 - 1
+        }
+    }
+
+    #[test]
+    fn test_dfs_path_is_connected() {
+        let graph = Line(10);
+        let path = dfs(&3, &graph).unwrap();
+        assert_eq!((Some(&3), Some(&9)), (path.first(), path.last()));
+        assert!(path.windows(2).all(|w| graph.moves(&w[0]).contains(&w[1])));
+        let grid = crate::FileReader::new("S...\n.##.\n...E\n").grid();
+        let graph = grid.graph(|c| *c != '#').with_goal(|_, c| *c == 'E');
+        let path = dfs(&crate::Vec2D(0, 0), &graph).unwrap();
+        assert_eq!(Some(&crate::Vec2D(2, 3)), path.last());
+        assert!(path.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+    }
+
+    #[test]
+    fn test_bfs_distances() {
+        let dist = bfs_distances(&3, &Line(10));
+        assert_eq!(10, dist.len());
+        assert_eq!((3, 0, 6), (dist[&0], dist[&3], dist[&9]));
+    }
+
+    #[test]
+    fn test_multi_source_limited() {
+        let dist = multi_source_bfs_limited([0, 9, 0], &Line(10), 2);
+        let mut reached = dist.keys().copied().collect::<Vec<_>>();
+        reached.sort();
+        assert_eq!(vec![0, 1, 2, 7, 8, 9], reached);
+        assert_eq!(1, dist[&8]);
     }
-    None
 }
@@ -0,0 +1,295 @@
+use std::{
+    cmp::Eq,
+    collections::{BinaryHeap, HashMap},
+    hash::Hash,
+};
+
+use super::{Cost, Graph, MinHeapState, Weighted, walk_path};
+
+/// Graphs whose moves can also be followed backwards, needed to search from the goal.
+pub trait ReversibleGraph: Graph {
+    /// Every node with a move to `node`.
+    fn predecessors(&self, node: &Self::Node) -> Vec<Self::Node>;
+}
+
+/// Marker for graphs where every move can be made in reverse,
+/// so the predecessors of a node are its moves.
+pub trait Undirected: Graph {}
+
+impl<G: Undirected> ReversibleGraph for G {
+    fn predecessors(&self, node: &Self::Node) -> Vec<Self::Node> {
+        self.moves(node)
+    }
+}
+
+/// One side of a bidirectional search.
+struct Side<N, C> {
+    dist: HashMap<N, C>,
+    parents: HashMap<N, N>,
+}
+
+impl<N: Eq + Hash + Clone, C: Cost> Side<N, C> {
+    fn new(node: &N) -> Self {
+        Self {
+            dist: HashMap::from([(node.clone(), C::zero())]),
+            parents: HashMap::new(),
+        }
+    }
+}
+
+/// Join the forward path from the start to `meet` with the backward path from `meet` to the goal.
+fn join<N: Eq + Hash + Clone>(
+    forward: &HashMap<N, N>,
+    backward: &HashMap<N, N>,
+    meet: N,
+) -> Vec<N> {
+    let mut path = walk_path(forward, meet.clone());
+    let mut rest = walk_path(backward, meet);
+    rest.reverse();
+    path.extend(rest.into_iter().skip(1));
+    path
+}
+
+/// Shortest path by number of moves found by searching from both ends at once,
+/// expanding a full layer of whichever side has the smaller frontier.
+/// Ignores [`Graph::is_done`].
+/// ```
+/// use puzlib::{FileReader, Vec2D, bidirectional_bfs};
+/// let grid = FileReader::new("....\n.##.\n....\n").grid();
+/// let graph = grid.graph(|c| *c != '#');
+/// let path = bidirectional_bfs(&Vec2D(1, 0), &Vec2D(1, 3), &graph).unwrap();
+/// assert_eq!(6, path.len());
+/// ```
+pub fn bidirectional_bfs<G: ReversibleGraph>(
+    start: &G::Node,
+    goal: &G::Node,
+    graph: &G,
+) -> Option<Vec<G::Node>> {
+    let mut forward: Side<G::Node, usize> = Side::new(start);
+    let mut backward: Side<G::Node, usize> = Side::new(goal);
+    let mut layers = (vec![start.clone()], vec![goal.clone()]);
+    if start == goal {
+        return Some(vec![start.clone()]);
+    }
+
+    while !layers.0.is_empty() && !layers.1.is_empty() {
+        let is_forward = layers.0.len() <= layers.1.len();
+        let (this, other, layer) = if is_forward {
+            (&mut forward, &backward, &mut layers.0)
+        } else {
+            (&mut backward, &forward, &mut layers.1)
+        };
+        let mut best: Option<(usize, G::Node)> = None;
+        let mut next_layer = Vec::new();
+        for node in layer.drain(..) {
+            let steps = this.dist[&node] + 1;
+            let next_moves = if is_forward {
+                graph.moves(&node)
+            } else {
+                graph.predecessors(&node)
+            };
+            for next_move in next_moves {
+                if this.dist.contains_key(&next_move) {
+                    continue;
+                }
+                this.dist.insert(next_move.clone(), steps);
+                this.parents.insert(next_move.clone(), node.clone());
+                if let Some(rest) = other.dist.get(&next_move)
+                    && best.as_ref().is_none_or(|(total, _)| steps + rest < *total)
+                {
+                    best = Some((steps + rest, next_move.clone()));
+                }
+                next_layer.push(next_move);
+            }
+        }
+        if let Some((_, meet)) = best {
+            return Some(join(&forward.parents, &backward.parents, meet));
+        }
+        *layer = next_layer;
+    }
+    None
+}
+
+/// Cheapest path found by running Dijkstra from the start and backwards from the goal,
+/// stopping once the two frontiers together cannot beat the best path found.
+/// Ignores [`Graph::is_done`]. Returns the cost and the path.
+pub fn bidirectional_dijkstra<N, G>(start: &N, goal: &N, graph: &G) -> Option<(G::Cost, Vec<N>)>
+where
+    N: Hash + Ord + Clone,
+    G: Weighted<Node = N> + ReversibleGraph,
+{
+    let mut forward: Side<N, G::Cost> = Side::new(start);
+    let mut backward: Side<N, G::Cost> = Side::new(goal);
+    let mut heaps = (BinaryHeap::new(), BinaryHeap::new());
+    heaps.0.push(MinHeapState {
+        node: start.clone(),
+        cost: G::Cost::zero(),
+    });
+    heaps.1.push(MinHeapState {
+        node: goal.clone(),
+        cost: G::Cost::zero(),
+    });
+    let mut best: Option<(G::Cost, N)> = (start == goal).then(|| (G::Cost::zero(), start.clone()));
+
+    while let (Some(top_forward), Some(top_backward)) = (heaps.0.peek(), heaps.1.peek()) {
+        let (top_forward, top_backward) = (top_forward.cost, top_backward.cost);
+        // Neither side can reach a cheaper meeting point.
+        if best
+            .as_ref()
+            .is_some_and(|(total, _)| top_forward.plus(top_backward) >= *total)
+        {
+            break;
+        }
+        let is_forward = top_forward <= top_backward;
+        let (this, other, heap) = if is_forward {
+            (&mut forward, &backward, &mut heaps.0)
+        } else {
+            (&mut backward, &forward, &mut heaps.1)
+        };
+        let MinHeapState { node, cost } = heap.pop().unwrap();
+        // Already have a better path to node.
+        if cost > this.dist[&node] {
+            continue;
+        }
+        let next_moves = if is_forward {
+            graph.moves(&node)
+        } else {
+            graph.predecessors(&node)
+        };
+        for next_move in next_moves {
+            let weight = if is_forward {
+                graph.weight(&node, &next_move)
+            } else {
+                graph.weight(&next_move, &node)
+            };
+            let next_cost = cost.plus(weight);
+            if this
+                .dist
+                .get(&next_move)
+                .is_none_or(|known| next_cost < *known)
+            {
+                heap.push(MinHeapState {
+                    node: next_move.clone(),
+                    cost: next_cost,
+                });
+                this.dist.insert(next_move.clone(), next_cost);
+                this.parents.insert(next_move.clone(), node.clone());
+                if let Some(rest) = other.dist.get(&next_move) {
+                    let total = next_cost.plus(*rest);
+                    if best.as_ref().is_none_or(|(known, _)| total < *known) {
+                        best = Some((total, next_move));
+                    }
+                }
+            }
+        }
+    }
+
+    best.map(|(total, meet)| (total, join(&forward.parents, &backward.parents, meet)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Numbers where each move doubles or adds one, reversed by halving or subtracting one.
+    struct Doubling(u64);
+
+    impl Graph for Doubling {
+        type Node = u64;
+
+        fn height(&self) -> usize {
+            1
+        }
+
+        fn width(&self) -> usize {
+            self.0 as usize
+        }
+
+        fn moves(&self, node: &u64) -> Vec<u64> {
+            [node * 2, node + 1]
+                .into_iter()
+                .filter(|n| *n <= self.0)
+                .collect()
+        }
+
+        fn is_done(&self, node: &u64) -> bool {
+            *node == self.0
+        }
+    }
+
+    impl ReversibleGraph for Doubling {
+        fn predecessors(&self, node: &u64) -> Vec<u64> {
+            let mut found = Vec::new();
+            if node.is_multiple_of(2) && *node > 0 {
+                found.push(node / 2);
+            }
+            if *node > 0 {
+                found.push(node - 1);
+            }
+            found
+        }
+    }
+
+    impl Weighted for Doubling {
+        type Cost = u64;
+
+        fn weight(&self, cur: &u64, next: &u64) -> u64 {
+            if *next == cur * 2 { 3 } else { 1 }
+        }
+    }
+
+    #[test]
+    fn test_bidirectional_bfs_directed() {
+        let graph = Doubling(1000);
+        let path = bidirectional_bfs(&1, &100, &graph).unwrap();
+        let expected = crate::bfs(&1, &Doubling(100)).unwrap();
+        assert_eq!(expected.len(), path.len());
+        assert_eq!((Some(&1), Some(&100)), (path.first(), path.last()));
+        assert!(path.windows(2).all(|w| graph.moves(&w[0]).contains(&w[1])));
+        assert_eq!(None, bidirectional_bfs(&5, &2, &graph));
+    }
+
+    #[test]
+    fn test_bidirectional_dijkstra_matches_dijkstra() {
+        let graph = Doubling(1000);
+        let (cost, path) = bidirectional_dijkstra(&1, &100, &graph).unwrap();
+        let (dist, _) = crate::dijkstra(&1, &Doubling(100)).unwrap();
+        assert_eq!(dist[&100], cost);
+        let summed = path
+            .windows(2)
+            .map(|w| graph.weight(&w[0], &w[1]))
+            .sum::<u64>();
+        assert_eq!(cost, summed);
+        assert_eq!(Some((0, vec![7])), bidirectional_dijkstra(&7, &7, &graph));
+    }
+
+    struct Ring(usize);
+
+    impl Graph for Ring {
+        type Node = usize;
+
+        fn height(&self) -> usize {
+            1
+        }
+
+        fn width(&self) -> usize {
+            self.0
+        }
+
+        fn moves(&self, node: &usize) -> Vec<usize> {
+            vec![(node + 1) % self.0, (node + self.0 - 1) % self.0]
+        }
+
+        fn is_done(&self, _: &usize) -> bool {
+            false
+        }
+    }
+
+    impl Undirected for Ring {}
+
+    #[test]
+    fn test_undirected_marker() {
+        let path = bidirectional_bfs(&1, &8, &Ring(10)).unwrap();
+        assert_eq!(vec![1, 0, 9, 8], path);
+    }
+}
@@ -0,0 +1,211 @@
+use std::{collections::HashMap, hash::Hash};
+
+use super::{Cost, Graph, Weighted};
+
+/// Nodes already explored in the current iteration with the cost they were reached at.
+/// Stops remembering new nodes once `cap` are stored; without a cap it remembers every node.
+struct Transpositions<N, C> {
+    seen: HashMap<N, C>,
+    cap: usize,
+}
+
+impl<N: Eq + Hash + Clone, C: PartialOrd + Copy> Transpositions<N, C> {
+    fn new(cap: Option<usize>) -> Self {
+        Self {
+            seen: HashMap::new(),
+            cap: cap.unwrap_or(usize::MAX),
+        }
+    }
+
+    /// Record a visit. Returns false if the node was already reached at no greater cost.
+    fn visit(&mut self, node: &N, cost: C) -> bool {
+        match self.seen.get_mut(node) {
+            Some(known) if *known <= cost => false,
+            Some(known) => {
+                *known = cost;
+                true
+            }
+            None => {
+                if self.seen.len() < self.cap {
+                    self.seen.insert(node.clone(), cost);
+                }
+                true
+            }
+        }
+    }
+}
+
+/// Moves of a node in reverse so popping them visits them in order.
+fn pending<G: Graph>(graph: &G, node: &G::Node) -> Vec<G::Node> {
+    let mut moves = graph.moves(node);
+    moves.reverse();
+    moves
+}
+
+/// Iterative deepening depth first search. Runs depth limited searches with a growing
+/// limit up to `max_depth` moves, so the path found has the fewest moves. A table of
+/// nodes already reached skips repeat visits within an iteration. `table` caps its size:
+/// `None` remembers every node seen, so memory grows like a breadth first search.
+/// `Some(cap)` bounds memory to the current path plus `cap` nodes, and `Some(0)` turns
+/// the table off, keeping only the current path in memory.
+/// ```
+/// use puzlib::{FileReader, Vec2D, iddfs};
+/// let grid = FileReader::new("S.#\n#..\n#.E\n").grid();
+/// let graph = grid.graph(|c| *c != '#').with_goal(|_, c| *c == 'E');
+/// assert_eq!(5, iddfs(&Vec2D(0, 0), &graph, 10, None).unwrap().len());
+/// assert_eq!(5, iddfs(&Vec2D(0, 0), &graph, 10, Some(0)).unwrap().len());
+/// assert_eq!(None, iddfs(&Vec2D(0, 0), &graph, 3, Some(100)));
+/// ```
+pub fn iddfs<G: Graph>(
+    start: &G::Node,
+    graph: &G,
+    max_depth: usize,
+    table: Option<usize>,
+) -> Option<Vec<G::Node>> {
+    if graph.is_done(start) {
+        return Some(vec![start.clone()]);
+    }
+    for limit in 1..=max_depth {
+        let mut seen = Transpositions::new(table);
+        let mut path = vec![start.clone()];
+        let mut to_visit = vec![pending(graph, start)];
+        let mut cut_off = false;
+        while let Some(moves) = to_visit.last_mut() {
+            let Some(next_move) = moves.pop() else {
+                to_visit.pop();
+                path.pop();
+                continue;
+            };
+            let depth = path.len();
+            if path.contains(&next_move) || !seen.visit(&next_move, depth) {
+                continue;
+            }
+            if graph.is_done(&next_move) {
+                path.push(next_move);
+                return Some(path);
+            }
+            if depth < limit {
+                to_visit.push(pending(graph, &next_move));
+            } else {
+                cut_off = true;
+                to_visit.push(Vec::new());
+            }
+            path.push(next_move);
+        }
+        // Every reachable node was within the limit.
+        if !cut_off {
+            return None;
+        }
+    }
+    None
+}
+
+/// Iterative deepening A*. Runs depth first searches that cut off paths whose cost plus
+/// heuristic exceeds a bound, raising the bound to the smallest cut off value each
+/// iteration. The heuristic must never overestimate for the path to be the cheapest.
+/// `table` caps the table of nodes reached in an iteration as in [`iddfs`]: `None`
+/// remembers every node seen, while `Some(cap)` keeps memory to the path length plus
+/// `cap` nodes and `Some(0)` to the path alone. Returns the cost and the path.
+pub fn ida_star<N, G, F>(
+    start: &N,
+    graph: &G,
+    heuristic: F,
+    table: Option<usize>,
+) -> Option<(G::Cost, Vec<N>)>
+where
+    N: Hash + Eq + Clone,
+    G: Weighted<Node = N>,
+    F: Fn(&N) -> G::Cost,
+{
+    if graph.is_done(start) {
+        return Some((G::Cost::zero(), vec![start.clone()]));
+    }
+    let mut bound = heuristic(start);
+    loop {
+        let mut seen = Transpositions::new(table);
+        let mut path = vec![start.clone()];
+        let mut costs = vec![G::Cost::zero()];
+        let mut to_visit = vec![pending(graph, start)];
+        let mut next_bound: Option<G::Cost> = None;
+        while let Some(moves) = to_visit.last_mut() {
+            let Some(next_move) = moves.pop() else {
+                to_visit.pop();
+                path.pop();
+                costs.pop();
+                continue;
+            };
+            let node = path.last().unwrap();
+            let cost = costs.last().unwrap().plus(graph.weight(node, &next_move));
+            let estimate = cost.plus(heuristic(&next_move));
+            if estimate > bound {
+                if next_bound.is_none_or(|next| estimate < next) {
+                    next_bound = Some(estimate);
+                }
+                continue;
+            }
+            if path.contains(&next_move) || !seen.visit(&next_move, cost) {
+                continue;
+            }
+            if graph.is_done(&next_move) {
+                path.push(next_move);
+                return Some((cost, path));
+            }
+            to_visit.push(pending(graph, &next_move));
+            path.push(next_move);
+            costs.push(cost);
+        }
+        bound = next_bound?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{FileReader, Vec2D, dijkstra};
+
+    use super::*;
+
+    #[test]
+    fn test_iddfs_shortest() {
+        let grid = FileReader::new(".....\n.###.\n.#E..\n.#.#.\n.....\n").grid();
+        let graph = grid.graph(|c| *c != '#').with_goal(|_, c| *c == 'E');
+        let expected = crate::bfs(&Vec2D(0, 0), &graph).unwrap().len();
+        assert_eq!(
+            Some(expected),
+            iddfs(&Vec2D(0, 0), &graph, 20, None).map(|p| p.len())
+        );
+        assert_eq!(
+            Some(expected),
+            iddfs(&Vec2D(0, 0), &graph, 20, Some(4)).map(|p| p.len())
+        );
+    }
+
+    #[test]
+    fn test_iddfs_unreachable_stops() {
+        let grid = FileReader::new("S#E\n").grid();
+        let graph = grid.graph(|c| *c != '#').with_goal(|_, c| *c == 'E');
+        assert_eq!(None, iddfs(&Vec2D(0, 0), &graph, usize::MAX, None));
+    }
+
+    #[test]
+    fn test_ida_star_matches_dijkstra() {
+        let grid = FileReader::new("1163751742\n1381373672\n2136511328\n3694931569\n7463417111\n")
+            .grid_with(|c| c.to_digit(10).unwrap() as usize);
+        let end = Vec2D(4, 9);
+        let graph = grid
+            .graph(|_| true)
+            .with_target(end)
+            .with_weight(|_, next| *next);
+        let (dist, _) = dijkstra(&Vec2D(0, 0), &graph).unwrap();
+        for table in [None, Some(1000)] {
+            let (cost, path) = ida_star(&Vec2D(0, 0), &graph, |n| n.manhattan(end), table).unwrap();
+            assert_eq!(dist[&end], cost);
+            assert_eq!(Some(&end), path.last());
+            let walked = path.windows(2).map(|w| grid[w[1]]).sum::<usize>();
+            assert_eq!(cost, walked);
+        }
+        assert_eq!(
+            Some((0, vec![end])),
+            ida_star(&end, &graph, |n| n.manhattan(end), Some(0))
+        );
+    }
+}
@@ -1,77 +1,249 @@
 use std::{
-    cmp::Eq,
-    collections::{BinaryHeap, HashMap, HashSet},
+    collections::{BinaryHeap, HashMap},
     hash::Hash,
 };
 
-use super::{Weighted, get_path};
+use super::{
+    Cost, CostedPath, MinHeapState, Observer, SearchStats, ShortestPathTree, ShortestPaths, Start,
+    Tracker, Weighted, walk_path,
+};
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct MinHeapState<S: Hash + Ord + PartialOrd + Eq + PartialEq> {
-    node: S,
-    cost: usize,
+/// Cheapest path from the start to the first node where [`Graph::is_done`](super::Graph::is_done)
+/// holds. Returns the best known cost of each node seen and the path taken.
+/// ```
+/// use puzlib::{FileReader, dijkstra};
+/// let grid = FileReader::new("S.#\n..E\n").grid();
+/// let graph = grid.graph(|c| *c != '#').with_goal(|_, c| *c == 'E');
+/// let (dist, path) = dijkstra(&grid.find(&'S').unwrap(), &graph).unwrap();
+/// assert_eq!(4, path.len());
+/// assert_eq!(3, dist[path.last().unwrap()]);
+/// ```
+pub fn dijkstra<N: Hash + Ord + PartialOrd + Clone, G: Weighted<Node = N>>(
+    start: &N,
+    graph: &G,
+) -> Option<CostedPath<N, G::Cost>> {
+    dijkstra_observed(start, graph, &mut ()).0
 }
 
-impl<S: Hash + Ord> Ord for MinHeapState<S> {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other
-            .cost
-            .cmp(&self.cost)
-            .then_with(|| self.node.cmp(&other.node))
-    }
+/// [`dijkstra`] reporting progress to an observer, with statistics about the search.
+pub fn dijkstra_observed<N: Hash + Ord + PartialOrd + Clone, G: Weighted<Node = N>>(
+    start: &N,
+    graph: &G,
+    observer: &mut impl Observer<N, G::Cost>,
+) -> (Option<CostedPath<N, G::Cost>>, SearchStats) {
+    dijkstra_search([start.clone()], graph, observer)
 }
 
-impl<S: Hash + Ord> PartialOrd for MinHeapState<S> {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
+/// [`dijkstra`] from any of several starts. Each start is a node or a `(node, initial cost)` pair.
+/// ```
+/// use puzlib::{FileReader, dijkstra_multi};
+/// let grid = FileReader::new("a.b\n..a\nE..\n").grid();
+/// let graph = grid.graph(|_| true).with_goal(|_, c| *c == 'E');
+/// let (_, path) = dijkstra_multi(grid.positions_of(&'a'), &graph).unwrap();
+/// assert_eq!(3, path.len());
+/// let (dist, path) = dijkstra_multi([(grid.find(&'b').unwrap(), 0), (grid.find(&'a').unwrap(), 5)], &graph).unwrap();
+/// assert_eq!(5, path.len());
+/// assert_eq!(4, dist[path.last().unwrap()]);
+/// ```
+pub fn dijkstra_multi<N: Hash + Ord + PartialOrd + Clone, G: Weighted<Node = N>>(
+    starts: impl IntoIterator<Item = impl Into<Start<N, G::Cost>>>,
+    graph: &G,
+) -> Option<CostedPath<N, G::Cost>> {
+    dijkstra_search(starts, graph, &mut ()).0
 }
 
-pub fn dijkstra<N: Hash + Ord + PartialOrd + Clone, G: Weighted<Node = N>>(
-    start: &N,
+fn dijkstra_search<N: Hash + Ord + PartialOrd + Clone, G: Weighted<Node = N>>(
+    starts: impl IntoIterator<Item = impl Into<Start<N, G::Cost>>>,
     graph: &G,
-) -> Option<(HashMap<N, usize>, Vec<N>)> {
-    let mut heap: BinaryHeap<MinHeapState<N>> = BinaryHeap::new();
-    let mut dist: HashMap<N, usize> = HashMap::new();
-    let mut index: HashSet<N> = HashSet::new();
+    observer: &mut impl Observer<N, G::Cost>,
+) -> (Option<CostedPath<N, G::Cost>>, SearchStats) {
+    let mut stats = Tracker::new();
+    let mut heap: BinaryHeap<MinHeapState<N, G::Cost>> = BinaryHeap::new();
+    let mut dist: HashMap<N, G::Cost> = HashMap::new();
     let mut path: HashMap<N, N> = HashMap::new();
 
-    heap.push(MinHeapState {
-        node: start.clone(),
-        cost: 0,
-    });
-    dist.insert(start.clone(), 0);
-    index.insert(start.clone());
+    for Start { node, cost } in starts.into_iter().map(Into::into) {
+        if dist.get(&node).is_none_or(|known| cost < *known) {
+            heap.push(MinHeapState {
+                node: node.clone(),
+                cost,
+            });
+            dist.insert(node, cost);
+        }
+    }
+    stats.frontier(heap.len());
 
     while let Some(MinHeapState { node, cost }) = heap.pop() {
         // Reached our goal.
         if graph.is_done(&node) {
-            return Some((dist, get_path(path, node, start)));
+            let found = !observer.on_goal(&node, cost).is_abort();
+            let path = walk_path(&path, node);
+            return (found.then_some((dist, path)), stats.finish());
         }
 
         // Already have a better path to node.
         if cost > dist[&node] {
             continue;
         }
+        stats.expand();
+        if observer.on_expand(&node, cost).is_abort() {
+            return (None, stats.finish());
+        }
         for next_move in graph.moves(&node) {
-            let next_cost = cost + graph.weight(&node, &next_move);
+            let next_cost = cost.plus(graph.weight(&node, &next_move));
             // Build the queue as we go instead of putting all nodes in at the start.
-            if index.insert(next_move.clone()) {
-                dist.insert(next_move.clone(), usize::MAX);
+            if dist.get(&next_move).is_none_or(|known| next_cost < *known) {
+                if observer.on_relax(&node, &next_move, next_cost).is_abort() {
+                    return (None, stats.finish());
+                }
+                heap.push(MinHeapState {
+                    node: next_move.clone(),
+                    cost: next_cost,
+                });
+                dist.insert(next_move.clone(), next_cost);
+                path.insert(next_move, node.clone());
             }
-            if next_cost < dist[&next_move] {
+        }
+        stats.frontier(heap.len());
+    }
+
+    (None, stats.finish())
+}
+
+/// Dijkstra run until every reachable node is found. Ignores [`Graph::is_done`](super::Graph::is_done).
+/// ```
+/// use puzlib::{FileReader, Vec2D, dijkstra_all};
+/// let grid = FileReader::new("S.#\n...\n").grid();
+/// let tree = dijkstra_all(&Vec2D(0, 0), &grid.graph(|c| *c != '#'));
+/// assert_eq!(Some(3), tree.cost(&Vec2D(1, 2)));
+/// assert_eq!(None, tree.cost(&Vec2D(0, 2)));
+/// assert_eq!(4, tree.path_to(&Vec2D(1, 2)).unwrap().len());
+/// ```
+pub fn dijkstra_all<N: Hash + Ord + PartialOrd + Clone, G: Weighted<Node = N>>(
+    start: &N,
+    graph: &G,
+) -> ShortestPathTree<N, G::Cost> {
+    dijkstra_tree(start, graph, None)
+}
+
+/// [`dijkstra_all`] that stops once the cheapest unexplored node costs more than `limit`.
+/// Only nodes costing at most `limit` are kept.
+pub fn dijkstra_bounded<N: Hash + Ord + PartialOrd + Clone, G: Weighted<Node = N>>(
+    start: &N,
+    graph: &G,
+    limit: G::Cost,
+) -> ShortestPathTree<N, G::Cost> {
+    dijkstra_tree(start, graph, Some(limit))
+}
+
+fn dijkstra_tree<N: Hash + Ord + PartialOrd + Clone, G: Weighted<Node = N>>(
+    start: &N,
+    graph: &G,
+    limit: Option<G::Cost>,
+) -> ShortestPathTree<N, G::Cost> {
+    let mut heap: BinaryHeap<MinHeapState<N, G::Cost>> = BinaryHeap::new();
+    let mut dist: HashMap<N, G::Cost> = HashMap::new();
+    let mut parents: HashMap<N, N> = HashMap::new();
+    let within = |cost: &G::Cost| limit.is_none_or(|limit| *cost <= limit);
+
+    heap.push(MinHeapState {
+        node: start.clone(),
+        cost: G::Cost::zero(),
+    });
+    dist.insert(start.clone(), G::Cost::zero());
+
+    while let Some(MinHeapState { node, cost }) = heap.pop() {
+        if !within(&cost) {
+            break;
+        }
+        // Already have a better path to node.
+        if cost > dist[&node] {
+            continue;
+        }
+        for next_move in graph.moves(&node) {
+            let next_cost = cost.plus(graph.weight(&node, &next_move));
+            if dist.get(&next_move).is_none_or(|known| next_cost < *known) {
                 heap.push(MinHeapState {
                     node: next_move.clone(),
                     cost: next_cost,
                 });
-                dist.entry(next_move.clone()).and_modify(|v| *v = next_cost);
-                let cur = path.entry(next_move.clone()).or_insert(node.clone());
-                *cur = node.clone();
+                dist.insert(next_move.clone(), next_cost);
+                parents.insert(next_move, node.clone());
             }
         }
     }
 
-    None
+    dist.retain(|_, cost| within(cost));
+    parents.retain(|node, _| dist.contains_key(node));
+    ShortestPathTree { dist, parents }
+}
+
+/// Dijkstra that keeps every equal cost predecessor so all shortest paths to the
+/// goal can be recovered. Runs until every goal node at the shortest cost is found.
+/// ```
+/// use puzlib::{FileReader, Vec2D, dijkstra_all_paths};
+/// let grid = FileReader::new("S..\n...\n..E\n").grid();
+/// let graph = grid.graph(|_| true).with_goal(|_, c| *c == 'E');
+/// let paths = dijkstra_all_paths(&Vec2D(0, 0), &graph).unwrap();
+/// assert_eq!((4, 6, 9), (paths.cost(), paths.count(), paths.nodes().len()));
+/// ```
+pub fn dijkstra_all_paths<N: Hash + Ord + PartialOrd + Clone, G: Weighted<Node = N>>(
+    start: &N,
+    graph: &G,
+) -> Option<ShortestPaths<N, G::Cost>> {
+    let mut heap: BinaryHeap<MinHeapState<N, G::Cost>> = BinaryHeap::new();
+    let mut dist: HashMap<N, G::Cost> = HashMap::new();
+    let mut preds: HashMap<N, Vec<N>> = HashMap::new();
+    let mut ends = Vec::new();
+    let mut best = None;
+
+    heap.push(MinHeapState {
+        node: start.clone(),
+        cost: G::Cost::zero(),
+    });
+    dist.insert(start.clone(), G::Cost::zero());
+
+    while let Some(MinHeapState { node, cost }) = heap.pop() {
+        // Every path from here on is longer than the best.
+        if best.is_some_and(|best| cost > best) {
+            break;
+        }
+        // Already have a better path to node.
+        if cost > dist[&node] {
+            continue;
+        }
+        if graph.is_done(&node) {
+            best = Some(cost);
+            ends.push(node);
+            continue;
+        }
+        for next_move in graph.moves(&node) {
+            let next_cost = cost.plus(graph.weight(&node, &next_move));
+            match dist.get(&next_move) {
+                // The start has no predecessor, even at the end of a zero cost cycle.
+                Some(_) if next_move == *start => (),
+                Some(known) if next_cost == *known => {
+                    preds.entry(next_move).or_default().push(node.clone());
+                }
+                Some(known) if next_cost > *known => (),
+                _ => {
+                    heap.push(MinHeapState {
+                        node: next_move.clone(),
+                        cost: next_cost,
+                    });
+                    dist.insert(next_move.clone(), next_cost);
+                    preds.insert(next_move, vec![node.clone()]);
+                }
+            }
+        }
+    }
+
+    best.map(|cost| ShortestPaths {
+        dist,
+        preds,
+        ends,
+        cost,
+    })
 }
 
 #[cfg(test)]
@@ -110,6 +282,157 @@ mod tests {
             dijkstra(&start, &graph).map(|g| (g.0[&graph.target], g.1))
         );
     }
+    #[test]
+    fn test_dijkstra_all_paths() {
+        // Two routes of cost 4 from 0 to 3 and one of cost 5.
+        let graph = Layout {
+            nodes: vec![
+                vec![(1, 1), (2, 3), (4, 1)],
+                vec![(3, 3)],
+                vec![(3, 1)],
+                vec![],
+                vec![(3, 4)],
+            ],
+            target: 3,
+        };
+        let paths = dijkstra_all_paths(&0, &graph).unwrap();
+        assert_eq!(4, paths.cost());
+        assert_eq!(2, paths.count());
+        let mut actual = paths.paths();
+        actual.sort();
+        assert_eq!(vec![vec![0, 1, 3], vec![0, 2, 3]], actual);
+        assert!(!paths.nodes().contains(&4));
+    }
+
+    #[test]
+    fn test_dijkstra_all_paths_zero_weights() {
+        // 0 -> 1 -> 2 and 0 -> 2 are free, so both reach 3 at cost 1.
+        let graph = Layout {
+            nodes: vec![vec![(1, 0), (2, 0)], vec![(2, 0)], vec![(3, 1)], vec![]],
+            target: 3,
+        };
+        let paths = dijkstra_all_paths(&0, &graph).unwrap();
+        assert_eq!(1, paths.cost());
+        assert_eq!(2, paths.count());
+        let mut actual = paths.paths();
+        actual.sort();
+        assert_eq!(vec![vec![0, 1, 2, 3], vec![0, 2, 3]], actual);
+    }
+
+    #[test]
+    fn test_dijkstra_all_paths_zero_cycle() {
+        // 1 and 2 reach each other for free, so 1 is its own predecessor through 2.
+        let graph = Layout {
+            nodes: vec![vec![(1, 1)], vec![(2, 0), (3, 1)], vec![(1, 0)], vec![]],
+            target: 3,
+        };
+        let paths = dijkstra_all_paths(&0, &graph).unwrap();
+        assert_eq!(2, paths.cost());
+        assert_eq!(vec![vec![0, 1, 3]], paths.paths());
+        assert_eq!(1, paths.count());
+        assert_eq!(1, dijkstra_all_paths(&1, &graph).unwrap().count());
+        // 0 -> 1 -> 2 -> 4 and 0 -> 1 -> 4 both cost 2 when 1 and 2 share a free cycle.
+        let graph = Layout {
+            nodes: vec![
+                vec![(1, 1)],
+                vec![(2, 0), (4, 1)],
+                vec![(1, 0), (4, 1)],
+                vec![],
+                vec![(3, 0)],
+            ],
+            target: 3,
+        };
+        let paths = dijkstra_all_paths(&0, &graph).unwrap();
+        let mut actual = paths.paths();
+        actual.sort();
+        assert_eq!(vec![vec![0, 1, 2, 4, 3], vec![0, 1, 4, 3]], actual);
+        assert_eq!(2, paths.count());
+    }
+
+    #[test]
+    fn test_dijkstra_all_and_bounded() {
+        let graph = Layout {
+            nodes: vec![
+                vec![(2, 10), (1, 1)],
+                vec![(3, 2)],
+                vec![(1, 1), (3, 3), (4, 1)],
+                vec![(0, 7), (4, 2)],
+                vec![],
+            ],
+            target: 0,
+        };
+        let tree = dijkstra_all(&2, &graph);
+        assert_eq!(5, tree.distances().len());
+        assert_eq!(Some(10), tree.cost(&0));
+        assert_eq!(Some(vec![2, 3, 0]), tree.path_to(&0));
+        assert_eq!(Some(vec![2]), tree.path_to(&2));
+        let bounded = dijkstra_bounded(&2, &graph, 3);
+        let mut reached = bounded.distances().keys().copied().collect::<Vec<_>>();
+        reached.sort();
+        assert_eq!(vec![1, 2, 3, 4], reached);
+        assert_eq!(None, bounded.path_to(&0));
+    }
+
+    #[test]
+    fn test_dijkstra_tuple_cost() {
+        // Fewer toll roads first, then shortest distance.
+        let graph = Roads(vec![
+            vec![(1, (1, 0.5)), (2, (0, 4.0))],
+            vec![(3, (0, 0.5))],
+            vec![(3, (0, 2.5))],
+            vec![],
+        ]);
+        let (dist, path) = dijkstra(&0, &graph).unwrap();
+        assert_eq!((0, 6.5), dist[&3]);
+        assert_eq!(vec![0, 2, 3], path);
+        let bounded = dijkstra_bounded(&0, &graph, (0, 5.0));
+        assert_eq!(Some((0, 4.0)), bounded.cost(&2));
+        assert_eq!(2, bounded.distances().len());
+    }
+
+    #[test]
+    fn test_dijkstra_nan_cost_sorts_last() {
+        let graph = Roads(vec![
+            vec![(1, (0, f64::NAN)), (2, (0, 1.0))],
+            vec![(3, (0, 0.0))],
+            vec![(3, (0, 1.0))],
+            vec![],
+        ]);
+        let (dist, path) = dijkstra(&0, &graph).unwrap();
+        assert_eq!((0, 2.0), dist[&3]);
+        assert_eq!(vec![0, 2, 3], path);
+    }
+
+    struct Roads(Vec<Vec<(usize, (u8, f64))>>);
+
+    impl Graph for Roads {
+        type Node = usize;
+
+        fn moves(&self, cur: &usize) -> Vec<usize> {
+            self.0[*cur].iter().map(|v| v.0).collect()
+        }
+
+        fn is_done(&self, node: &usize) -> bool {
+            *node == self.0.len() - 1
+        }
+
+        fn height(&self) -> usize {
+            1
+        }
+
+        fn width(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    impl Weighted for Roads {
+        type Cost = (u8, f64);
+
+        fn weight(&self, cur: &usize, next: &usize) -> Self::Cost {
+            self.0[*cur].iter().find(|v| v.0 == *next).unwrap().1
+        }
+    }
+
     struct Layout {
         nodes: Vec<Vec<(usize, usize)>>,
         target: usize,
@@ -136,6 +459,8 @@ mod tests {
     }
 
     impl Weighted for Layout {
+        type Cost = usize;
+
         fn weight(&self, cur: &usize, next: &usize) -> usize {
             self.nodes[*cur]
                 .iter()
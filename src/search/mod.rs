@@ -1,16 +1,41 @@
-use std::{cmp::Eq, collections::HashMap, hash::Hash};
+use std::{
+    cmp::{Eq, Ordering},
+    collections::HashMap,
+    hash::Hash,
+};
 
 mod median;
 pub use median::*;
 
+mod observer;
+use observer::Tracker;
+pub use observer::{Control, Observer, SearchStats};
+
 mod basic;
 pub use basic::*;
 
 mod dijkstra;
-pub use dijkstra::dijkstra;
+pub use dijkstra::{
+    dijkstra, dijkstra_all, dijkstra_all_paths, dijkstra_bounded, dijkstra_multi, dijkstra_observed,
+};
 
 mod a_star;
-pub use a_star::a_star;
+pub use a_star::{a_star, a_star_all_paths, a_star_multi, a_star_observed};
+
+mod traversal;
+pub use traversal::{
+    DfsEvent, DfsEvents, EdgeKind, dfs_events, dfs_events_from, has_cycle, has_undirected_cycle,
+    topological_order,
+};
+
+mod deepening;
+pub use deepening::{ida_star, iddfs};
+
+mod bidirectional;
+pub use bidirectional::{ReversibleGraph, Undirected, bidirectional_bfs, bidirectional_dijkstra};
+
+mod paths;
+pub use paths::{ShortestPathTree, ShortestPaths};
 
 pub trait Graph {
     type Node: Eq + Hash + Clone;
@@ -21,9 +46,133 @@ pub trait Graph {
 }
 
 pub trait Weighted: Graph {
-    fn weight(&self, cur: &Self::Node, next: &Self::Node) -> usize;
+    type Cost: Cost;
+    fn weight(&self, cur: &Self::Node, next: &Self::Node) -> Self::Cost;
+}
+
+/// A node to start a weighted search from, with the cost already spent reaching it.
+/// Multi start searches such as [`dijkstra_multi`] take anything that converts into a
+/// `Start`, so a bare node starts at zero and a `(node, cost)` pair starts at that cost.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Start<N, C> {
+    pub node: N,
+    pub cost: C,
+}
+
+impl<N, C: Cost> From<N> for Start<N, C> {
+    fn from(node: N) -> Self {
+        Self {
+            node,
+            cost: C::zero(),
+        }
+    }
+}
+
+impl<N, C> From<(N, C)> for Start<N, C> {
+    fn from((node, cost): (N, C)) -> Self {
+        Self { node, cost }
+    }
+}
+
+/// Queue entry for weighted searches. Orders by lowest cost first so a `BinaryHeap` pops
+/// the cheapest node, breaking ties by node.
+#[derive(Debug, Clone)]
+pub(crate) struct MinHeapState<S, C> {
+    pub(crate) node: S,
+    pub(crate) cost: C,
+}
+
+impl<S: Ord, C: Cost> Ord for MinHeapState<S, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cost
+            .total_cmp(&self.cost)
+            .then_with(|| self.node.cmp(&other.node))
+    }
+}
+
+impl<S: Ord, C: Cost> PartialOrd for MinHeapState<S, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S: Ord, C: Cost> PartialEq for MinHeapState<S, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S: Ord, C: Cost> Eq for MinHeapState<S, C> {}
+
+/// Best known cost of every node seen by a search, and the path it found.
+pub type CostedPath<N, C> = (HashMap<N, C>, Vec<N>);
+
+/// Path costs that weighted searches can add up and compare.
+/// Implemented for all numbers and for tuples of costs, which compare lexicographically.
+/// Searches order their queues with [`Cost::total_cmp`], so a NaN float cost sorts
+/// after every other cost instead of breaking the queue.
+/// ```
+/// use puzlib::Cost;
+/// use std::cmp::Ordering;
+/// assert_eq!((3, 1.5), (1, 0.5).plus((2, 1.0)));
+/// assert!((1, 9) < (2, 0));
+/// assert_eq!(Ordering::Less, 1.0.total_cmp(&f64::NAN));
+/// assert_eq!(0_u64, u64::zero());
+/// ```
+pub trait Cost: Copy + PartialOrd {
+    fn zero() -> Self;
+    fn plus(self, other: Self) -> Self;
+    /// A total order over costs, agreeing with `PartialOrd` wherever it is defined.
+    fn total_cmp(&self, other: &Self) -> Ordering;
+}
+
+macro_rules! cost_impl {
+    ($zero:expr, $cmp:ident; $($t:ty) *) => ($(
+        impl Cost for $t {
+            #[inline]
+            fn zero() -> Self {
+                $zero
+            }
+
+            #[inline]
+            fn plus(self, other: Self) -> Self {
+                self + other
+            }
+
+            #[inline]
+            fn total_cmp(&self, other: &Self) -> Ordering {
+                <$t>::$cmp(self, other)
+            }
+        }
+    )*)
+}
+
+cost_impl!(0, cmp; u8 u16 u32 u64 usize u128 i8 i16 i32 i64 isize i128);
+cost_impl!(0.0, total_cmp; f32 f64);
+
+macro_rules! cost_tuple_impl {
+    ($($t:ident $idx:tt),+) => {
+        impl<$($t: Cost),+> Cost for ($($t,)+) {
+            fn zero() -> Self {
+                ($($t::zero(),)+)
+            }
+
+            fn plus(self, other: Self) -> Self {
+                ($(self.$idx.plus(other.$idx),)+)
+            }
+
+            fn total_cmp(&self, other: &Self) -> Ordering {
+                Ordering::Equal $(.then_with(|| self.$idx.total_cmp(&other.$idx)))+
+            }
+        }
+    };
 }
 
+cost_tuple_impl!(A 0, B 1);
+cost_tuple_impl!(A 0, B 1, C 2);
+cost_tuple_impl!(A 0, B 1, C 2, D 3);
+
 pub fn get_path<S: PartialEq + Eq + Hash + Clone>(
     moves: HashMap<S, S>,
     end: S,
@@ -40,3 +189,13 @@ pub fn get_path<S: PartialEq + Eq + Hash + Clone>(
     found.reverse();
     found
 }
+
+/// Follow predecessors back from `end` until a node without one, such as a start, is reached.
+pub(crate) fn walk_path<S: Eq + Hash + Clone>(moves: &HashMap<S, S>, end: S) -> Vec<S> {
+    let mut found = vec![end];
+    while let Some(node) = moves.get(found.last().unwrap()) {
+        found.push(node.clone());
+    }
+    found.reverse();
+    found
+}
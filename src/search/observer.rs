@@ -0,0 +1,217 @@
+use std::time::{Duration, Instant};
+
+/// What a search should do after an [`Observer`] callback.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Control {
+    #[default]
+    Continue,
+    /// Stop the search straight away without a result.
+    Abort,
+}
+
+impl Control {
+    pub fn is_abort(&self) -> bool {
+        *self == Self::Abort
+    }
+}
+
+/// Callbacks from a running search, for tracing it or stopping it early.
+/// Costs are the number of moves for [`bfs`](super::bfs) and [`dfs`](super::dfs)
+/// and the path cost for weighted searches. Every callback continues by default,
+/// and `()` is an observer that does nothing.
+/// ```
+/// use puzlib::{Control, FileReader, Observer, Vec2D, bfs_observed};
+/// struct Budget(usize);
+/// impl Observer<Vec2D<usize>, usize> for Budget {
+///     fn on_expand(&mut self, _: &Vec2D<usize>, _: usize) -> Control {
+///         self.0 -= 1;
+///         if self.0 == 0 { Control::Abort } else { Control::Continue }
+///     }
+/// }
+/// let grid = FileReader::new("S...\n....\n...E\n").grid();
+/// let graph = grid.graph(|_| true).with_goal(|_, c| *c == 'E');
+/// let (path, stats) = bfs_observed(&Vec2D(0, 0), &graph, &mut Budget(3));
+/// assert_eq!((None, 3), (path, stats.expanded));
+/// let (path, stats) = bfs_observed(&Vec2D(0, 0), &graph, &mut ());
+/// assert_eq!(Some(6), path.map(|p| p.len()));
+/// assert!(stats.peak_frontier >= 2);
+/// ```
+pub trait Observer<N, C> {
+    /// A node was taken from the frontier and is about to have its moves generated.
+    fn on_expand(&mut self, _node: &N, _cost: C) -> Control {
+        Control::Continue
+    }
+
+    /// A new or cheaper way to reach `next` from `from` was found.
+    fn on_relax(&mut self, _from: &N, _next: &N, _cost: C) -> Control {
+        Control::Continue
+    }
+
+    /// A goal was reached. The search finishes here either way;
+    /// [`Control::Abort`] discards the result.
+    fn on_goal(&mut self, _node: &N, _cost: C) -> Control {
+        Control::Continue
+    }
+}
+
+impl<N, C> Observer<N, C> for () {}
+
+/// Counters reported by the `_observed` searches.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct SearchStats {
+    /// Nodes taken from the frontier and expanded.
+    pub expanded: usize,
+    /// Largest number of entries waiting in the frontier at once.
+    pub peak_frontier: usize,
+    pub elapsed: Duration,
+}
+
+/// Collects [`SearchStats`] while a search runs.
+pub(crate) struct Tracker {
+    started: Instant,
+    stats: SearchStats,
+}
+
+impl Tracker {
+    pub(crate) fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            stats: SearchStats::default(),
+        }
+    }
+
+    pub(crate) fn expand(&mut self) {
+        self.stats.expanded += 1;
+    }
+
+    pub(crate) fn frontier(&mut self, len: usize) {
+        self.stats.peak_frontier = self.stats.peak_frontier.max(len);
+    }
+
+    pub(crate) fn finish(mut self) -> SearchStats {
+        self.stats.elapsed = self.started.elapsed();
+        self.stats
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        FileReader, Graph, Vec2D, Weighted, a_star_observed, dfs_observed, dijkstra_observed,
+    };
+
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorder {
+        expanded: Vec<Vec2D<usize>>,
+        relaxed: usize,
+        goal: Option<usize>,
+    }
+
+    impl Observer<Vec2D<usize>, usize> for Recorder {
+        fn on_expand(&mut self, node: &Vec2D<usize>, _: usize) -> Control {
+            self.expanded.push(*node);
+            Control::Continue
+        }
+
+        fn on_relax(&mut self, _: &Vec2D<usize>, _: &Vec2D<usize>, _: usize) -> Control {
+            self.relaxed += 1;
+            Control::Continue
+        }
+
+        fn on_goal(&mut self, _: &Vec2D<usize>, cost: usize) -> Control {
+            self.goal = Some(cost);
+            Control::Continue
+        }
+    }
+
+    #[test]
+    fn test_weighted_observers() {
+        let grid = FileReader::new("S..\n.#.\n..E\n").grid();
+        let end = Vec2D(2, 2);
+        let graph = grid.graph(|c| *c != '#').with_target(end);
+        let mut recorder = Recorder::default();
+        let (found, stats) = dijkstra_observed(&Vec2D(0, 0), &graph, &mut recorder);
+        assert_eq!(Some(4), found.map(|(dist, _)| dist[&end]));
+        assert_eq!(Some(4), recorder.goal);
+        assert_eq!(recorder.expanded.len(), stats.expanded);
+        assert_eq!(Some(&Vec2D(0, 0)), recorder.expanded.first());
+        assert!(recorder.relaxed >= 7);
+
+        let mut guided = Recorder::default();
+        let (_, guided_stats) =
+            a_star_observed(&Vec2D(0, 0), &graph, |n| n.manhattan(end), &mut guided);
+        assert_eq!(Some(4), guided.goal);
+        assert!(guided_stats.expanded <= stats.expanded);
+    }
+
+    /// Weighted edges between numbered nodes. The last node is the goal.
+    struct Edges(Vec<Vec<(usize, usize)>>);
+
+    impl Graph for Edges {
+        type Node = usize;
+
+        fn moves(&self, cur: &usize) -> Vec<usize> {
+            self.0[*cur].iter().map(|v| v.0).collect()
+        }
+
+        fn is_done(&self, node: &usize) -> bool {
+            *node == self.0.len() - 1
+        }
+
+        fn height(&self) -> usize {
+            1
+        }
+
+        fn width(&self) -> usize {
+            self.0.len()
+        }
+    }
+
+    impl Weighted for Edges {
+        type Cost = usize;
+
+        fn weight(&self, cur: &usize, next: &usize) -> usize {
+            self.0[*cur].iter().find(|v| v.0 == *next).unwrap().1
+        }
+    }
+
+    impl Observer<usize, usize> for Vec<usize> {
+        fn on_expand(&mut self, node: &usize, _: usize) -> Control {
+            self.push(*node);
+            Control::Continue
+        }
+    }
+
+    #[test]
+    fn test_improved_nodes_expand_once() {
+        // 1 is first queued at cost 5, then improved to 2 through 2.
+        let graph = Edges(vec![
+            vec![(1, 5), (2, 1)],
+            vec![(3, 10)],
+            vec![(1, 1)],
+            vec![],
+        ]);
+        let mut expanded = Vec::new();
+        let (_, stats) = a_star_observed(&0, &graph, |_| 0, &mut expanded);
+        assert_eq!(vec![0, 2, 1], expanded);
+        assert_eq!(3, stats.expanded);
+        let (_, stats) = dijkstra_observed(&0, &graph, &mut Vec::new());
+        assert_eq!(3, stats.expanded);
+    }
+
+    #[test]
+    fn test_abort_on_goal() {
+        struct Reject;
+        impl Observer<Vec2D<usize>, usize> for Reject {
+            fn on_goal(&mut self, _: &Vec2D<usize>, _: usize) -> Control {
+                Control::Abort
+            }
+        }
+        let grid = FileReader::new("S.E\n").grid();
+        let graph = grid.graph(|_| true).with_goal(|_, c| *c == 'E');
+        assert_eq!(None, dfs_observed(&Vec2D(0, 0), &graph, &mut Reject).0);
+        assert_eq!(None, dijkstra_observed(&Vec2D(0, 0), &graph, &mut Reject).0);
+    }
+}
@@ -0,0 +1,156 @@
+use std::{
+    collections::{HashMap, HashSet},
+    hash::Hash,
+};
+
+use super::Cost;
+
+/// Distances from a start to every node a search reached, with the predecessor
+/// each node was reached from. Returned by [`dijkstra_all`](super::dijkstra_all)
+/// and [`dijkstra_bounded`](super::dijkstra_bounded).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortestPathTree<N: Eq + Hash, C = usize> {
+    pub(crate) dist: HashMap<N, C>,
+    pub(crate) parents: HashMap<N, N>,
+}
+
+impl<N: Eq + Hash + Clone, C: Cost> ShortestPathTree<N, C> {
+    /// Cost to reach a node, or `None` if it was not reached.
+    pub fn cost(&self, node: &N) -> Option<C> {
+        self.dist.get(node).copied()
+    }
+
+    pub fn distances(&self) -> &HashMap<N, C> {
+        &self.dist
+    }
+
+    pub fn into_distances(self) -> HashMap<N, C> {
+        self.dist
+    }
+
+    /// The node each reached node was reached from. The start has none.
+    pub fn parents(&self) -> &HashMap<N, N> {
+        &self.parents
+    }
+
+    /// A shortest path from the start to `node`, or `None` if it was not reached.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        if !self.dist.contains_key(node) {
+            return None;
+        }
+        let mut path = vec![node.clone()];
+        while let Some(parent) = self.parents.get(path.last().unwrap()) {
+            path.push(parent.clone());
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// Every shortest path found by a search that keeps all equal cost predecessors.
+/// Returned by [`dijkstra_all_paths`](super::dijkstra_all_paths) and
+/// [`a_star_all_paths`](super::a_star_all_paths). Steps may cost 0. Paths never visit
+/// a node twice, so a cycle of zero cost steps is not followed around.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShortestPaths<N: Eq + Hash, C = usize> {
+    pub(crate) dist: HashMap<N, C>,
+    pub(crate) preds: HashMap<N, Vec<N>>,
+    pub(crate) ends: Vec<N>,
+    pub(crate) cost: C,
+}
+
+impl<N: Eq + Hash + Clone, C: Cost> ShortestPaths<N, C> {
+    /// Cost of the shortest paths.
+    pub fn cost(&self) -> C {
+        self.cost
+    }
+
+    /// Goal nodes reached at the shortest cost.
+    pub fn ends(&self) -> &[N] {
+        &self.ends
+    }
+
+    /// Best known cost of every node seen by the search.
+    pub fn distances(&self) -> &HashMap<N, C> {
+        &self.dist
+    }
+
+    /// Every node's equal cost predecessors. The start has none.
+    pub fn predecessors(&self) -> &HashMap<N, Vec<N>> {
+        &self.preds
+    }
+
+    /// Every node on at least one shortest path, including start and ends.
+    pub fn nodes(&self) -> HashSet<N> {
+        let mut seen: HashSet<N> = self.ends.iter().cloned().collect();
+        let mut to_visit = self.ends.clone();
+        while let Some(node) = to_visit.pop() {
+            for pred in self.preds.get(&node).into_iter().flatten() {
+                if seen.insert(pred.clone()) {
+                    to_visit.push(pred.clone());
+                }
+            }
+        }
+        seen
+    }
+
+    /// Number of distinct shortest paths, counted without listing them.
+    /// Always equals the length of [`ShortestPaths::paths`]. If zero cost steps form a
+    /// cycle the paths through it have to be listed to be counted.
+    pub fn count(&self) -> usize {
+        let mut counts: HashMap<&N, usize> = HashMap::new();
+        let mut expanded: HashSet<&N> = HashSet::new();
+        // Post-order walk so every predecessor is counted before the nodes after it.
+        // Zero cost steps can leave a node and its predecessor at the same distance,
+        // so ordering by distance alone is not enough.
+        let mut to_visit = self.ends.iter().map(|end| (end, false)).collect::<Vec<_>>();
+        while let Some((node, done)) = to_visit.pop() {
+            if counts.contains_key(node) {
+                continue;
+            }
+            let preds = self.preds.get(node).map_or(&[][..], Vec::as_slice);
+            if preds.is_empty() {
+                counts.insert(node, 1);
+            } else if done {
+                // A predecessor still being expanded closes a zero cost cycle.
+                let Some(count) = preds.iter().map(|p| counts.get(p)).sum::<Option<usize>>() else {
+                    return self.paths().len();
+                };
+                counts.insert(node, count);
+            } else if expanded.insert(node) {
+                to_visit.push((node, true));
+                to_visit.extend(preds.iter().map(|p| (p, false)));
+            }
+        }
+        self.ends.iter().map(|end| counts[end]).sum()
+    }
+
+    /// Every shortest path from start to end, none visiting a node twice. The number of
+    /// paths can grow exponentially; use [`ShortestPaths::count`] if only the number is needed.
+    pub fn paths(&self) -> Vec<Vec<N>> {
+        let mut found = Vec::new();
+        let mut to_visit = self
+            .ends
+            .iter()
+            .map(|end| vec![end.clone()])
+            .collect::<Vec<_>>();
+        while let Some(path) = to_visit.pop() {
+            match self.preds.get(path.last().unwrap()) {
+                Some(preds) if !preds.is_empty() => {
+                    // Predecessors already on the path lead around a zero cost cycle.
+                    for pred in preds.iter().filter(|p| !path.contains(p)) {
+                        let mut next = path.clone();
+                        next.push(pred.clone());
+                        to_visit.push(next);
+                    }
+                }
+                _ => {
+                    let mut path = path;
+                    path.reverse();
+                    found.push(path);
+                }
+            }
+        }
+        found
+    }
+}
@@ -0,0 +1,343 @@
+use std::collections::HashMap;
+
+use super::Graph;
+
+/// How an edge met during a depth first traversal relates to the traversal tree.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EdgeKind {
+    /// Leads to a node seen for the first time.
+    Tree,
+    /// Leads to a node still being explored, closing a cycle.
+    /// In an undirected graph the edge back to the parent is reported as one too.
+    Back,
+    /// Leads to an already finished descendant.
+    Forward,
+    /// Leads to an already finished node in another branch or tree.
+    Cross,
+}
+
+/// A step of a depth first traversal. Times come from one counter that ticks on
+/// every discovery and finish, so a node's descendants are discovered and finished
+/// between its own discovery and finish times.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DfsEvent<N> {
+    /// First visit of a node (pre-order).
+    Discover { node: N, time: usize },
+    /// A move from one node to another, reported before any discovery it leads to.
+    Edge { from: N, to: N, kind: EdgeKind },
+    /// All moves of a node have been explored (post-order).
+    Finish { node: N, time: usize },
+}
+
+/// Lazy depth first traversal of a graph. Build one with [`dfs_events`] or [`dfs_events_from`].
+/// Moves are followed in the order the graph gives them. Ignores [`Graph::is_done`].
+pub struct DfsEvents<'a, G: Graph> {
+    graph: &'a G,
+    roots: std::vec::IntoIter<G::Node>,
+    stack: Vec<(G::Node, std::vec::IntoIter<G::Node>)>,
+    discovered: HashMap<G::Node, usize>,
+    finished: HashMap<G::Node, usize>,
+    queued: Option<DfsEvent<G::Node>>,
+    time: usize,
+}
+
+/// Depth first traversal of everything reachable from `start`.
+/// ```
+/// use puzlib::{DfsEvent, FileReader, Vec2D, dfs_events};
+/// let grid = FileReader::new("...\n").grid();
+/// let graph = grid.graph(|_| true);
+/// let finished = dfs_events(&Vec2D(0, 0), &graph)
+///     .filter_map(|e| match e {
+///         DfsEvent::Finish { node, .. } => Some(node.1),
+///         _ => None,
+///     })
+///     .collect::<Vec<_>>();
+/// assert_eq!(vec![2, 1, 0], finished);
+/// ```
+pub fn dfs_events<'a, G: Graph>(start: &G::Node, graph: &'a G) -> DfsEvents<'a, G> {
+    dfs_events_from([start.clone()], graph)
+}
+
+/// Depth first traversal from each root in turn, skipping roots already reached.
+pub fn dfs_events_from<'a, G: Graph>(
+    roots: impl IntoIterator<Item = G::Node>,
+    graph: &'a G,
+) -> DfsEvents<'a, G> {
+    DfsEvents {
+        graph,
+        roots: roots.into_iter().collect::<Vec<_>>().into_iter(),
+        stack: Vec::new(),
+        discovered: HashMap::new(),
+        finished: HashMap::new(),
+        queued: None,
+        time: 0,
+    }
+}
+
+impl<G: Graph> DfsEvents<'_, G> {
+    fn discover(&mut self, node: G::Node) -> DfsEvent<G::Node> {
+        self.time += 1;
+        self.discovered.insert(node.clone(), self.time);
+        let moves = self.graph.moves(&node).into_iter();
+        self.stack.push((node.clone(), moves));
+        DfsEvent::Discover {
+            node,
+            time: self.time,
+        }
+    }
+
+    /// Discovery time of a node, if it has been reached.
+    pub fn discovery_time(&self, node: &G::Node) -> Option<usize> {
+        self.discovered.get(node).copied()
+    }
+
+    /// Finish time of a node, if all its moves have been explored.
+    pub fn finish_time(&self, node: &G::Node) -> Option<usize> {
+        self.finished.get(node).copied()
+    }
+}
+
+impl<G: Graph> Iterator for DfsEvents<'_, G> {
+    type Item = DfsEvent<G::Node>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(event) = self.queued.take() {
+            return Some(event);
+        }
+        loop {
+            let Some((node, moves)) = self.stack.last_mut() else {
+                let root = self.roots.next()?;
+                if self.discovered.contains_key(&root) {
+                    continue;
+                }
+                return Some(self.discover(root));
+            };
+            let Some(next) = moves.next() else {
+                let (node, _) = self.stack.pop().unwrap();
+                self.time += 1;
+                self.finished.insert(node.clone(), self.time);
+                return Some(DfsEvent::Finish {
+                    node,
+                    time: self.time,
+                });
+            };
+            let from = node.clone();
+            let kind = match self.discovered.get(&next) {
+                None => EdgeKind::Tree,
+                Some(_) if !self.finished.contains_key(&next) => EdgeKind::Back,
+                Some(time) if *time > self.discovered[&from] => EdgeKind::Forward,
+                Some(_) => EdgeKind::Cross,
+            };
+            if kind == EdgeKind::Tree {
+                let discovered = self.discover(next.clone());
+                self.queued = Some(discovered);
+            }
+            return Some(DfsEvent::Edge {
+                from,
+                to: next,
+                kind,
+            });
+        }
+    }
+}
+
+/// Whether any cycle can be reached from the roots, following moves in one direction.
+/// Meant for directed graphs: in an undirected graph every edge leads back to its
+/// parent and counts as a cycle, so use [`has_undirected_cycle`] there.
+pub fn has_cycle<G: Graph>(roots: impl IntoIterator<Item = G::Node>, graph: &G) -> bool {
+    dfs_events_from(roots, graph).any(|e| {
+        matches!(
+            e,
+            DfsEvent::Edge {
+                kind: EdgeKind::Back,
+                ..
+            }
+        )
+    })
+}
+
+/// Whether any cycle can be reached from the roots in a graph where every move can be
+/// made in reverse, such as a grid. Going straight back to the node a node was reached
+/// from does not count, so a cycle needs at least three nodes.
+/// ```
+/// use puzlib::{FileReader, Vec2D, has_cycle, has_undirected_cycle};
+/// let grid = FileReader::new("..#\n#..\n").grid();
+/// let graph = grid.graph(|c| *c != '#');
+/// assert!(has_cycle([Vec2D(0, 0)], &graph));
+/// assert!(!has_undirected_cycle([Vec2D(0, 0)], &graph));
+/// let open = FileReader::new("..\n..\n").grid();
+/// assert!(has_undirected_cycle([Vec2D(0, 0)], &open.graph(|_| true)));
+/// ```
+pub fn has_undirected_cycle<G: Graph>(roots: impl IntoIterator<Item = G::Node>, graph: &G) -> bool {
+    let mut parents = HashMap::new();
+    dfs_events_from(roots, graph).any(|e| match e {
+        DfsEvent::Edge {
+            from,
+            to,
+            kind: EdgeKind::Tree,
+        } => {
+            parents.insert(to, from);
+            false
+        }
+        DfsEvent::Edge {
+            from,
+            to,
+            kind: EdgeKind::Back,
+        } => parents.get(&from) != Some(&to),
+        _ => false,
+    })
+}
+
+/// Every node reachable from the roots ordered so each comes before all nodes it
+/// has a move to. Returns `None` if there is a cycle.
+/// ```
+/// use puzlib::{Graph, topological_order};
+/// struct Steps(Vec<Vec<usize>>);
+/// impl Graph for Steps {
+///     type Node = usize;
+///     fn height(&self) -> usize { 1 }
+///     fn width(&self) -> usize { self.0.len() }
+///     fn moves(&self, node: &usize) -> Vec<usize> { self.0[*node].clone() }
+///     fn is_done(&self, _: &usize) -> bool { false }
+/// }
+/// let steps = Steps(vec![vec![3], vec![0, 3], vec![1], vec![]]);
+/// assert_eq!(Some(vec![2, 1, 0, 3]), topological_order(0..4, &steps));
+/// assert_eq!(None, topological_order(0..2, &Steps(vec![vec![1], vec![0]])));
+/// ```
+pub fn topological_order<G: Graph>(
+    roots: impl IntoIterator<Item = G::Node>,
+    graph: &G,
+) -> Option<Vec<G::Node>> {
+    let mut order = Vec::new();
+    for event in dfs_events_from(roots, graph) {
+        match event {
+            DfsEvent::Edge {
+                kind: EdgeKind::Back,
+                ..
+            } => return None,
+            DfsEvent::Finish { node, .. } => order.push(node),
+            _ => (),
+        }
+    }
+    order.reverse();
+    Some(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct Digraph(Vec<Vec<usize>>);
+
+    impl Graph for Digraph {
+        type Node = usize;
+
+        fn height(&self) -> usize {
+            1
+        }
+
+        fn width(&self) -> usize {
+            self.0.len()
+        }
+
+        fn moves(&self, node: &usize) -> Vec<usize> {
+            self.0[*node].clone()
+        }
+
+        fn is_done(&self, _: &usize) -> bool {
+            false
+        }
+    }
+
+    fn edges(
+        graph: &Digraph,
+        roots: impl IntoIterator<Item = usize>,
+    ) -> Vec<(usize, usize, EdgeKind)> {
+        dfs_events_from(roots, graph)
+            .filter_map(|e| match e {
+                DfsEvent::Edge { from, to, kind } => Some((from, to, kind)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_edge_kinds() {
+        // 0 -> 1 -> 2 -> 0 is a cycle, 0 -> 2 skips ahead, 3 -> 2 crosses over.
+        let graph = Digraph(vec![vec![1, 2], vec![2], vec![0], vec![2]]);
+        let expected = vec![
+            (0, 1, EdgeKind::Tree),
+            (1, 2, EdgeKind::Tree),
+            (2, 0, EdgeKind::Back),
+            (0, 2, EdgeKind::Forward),
+            (3, 2, EdgeKind::Cross),
+        ];
+        assert_eq!(expected, edges(&graph, 0..4));
+        assert!(has_cycle([0], &graph));
+        assert!(!has_cycle(
+            [3],
+            &Digraph(vec![vec![], vec![], vec![], vec![2]])
+        ));
+    }
+
+    #[test]
+    fn test_undirected_cycles() {
+        // A path 0 - 1 - 2 with every edge listed both ways.
+        let path = Digraph(vec![vec![1], vec![0, 2], vec![1]]);
+        assert!(has_cycle([0], &path));
+        assert!(!has_undirected_cycle([0], &path));
+        let triangle = Digraph(vec![vec![1, 2], vec![0, 2], vec![0, 1]]);
+        assert!(has_undirected_cycle([0], &triangle));
+        assert!(!has_undirected_cycle(
+            0..3,
+            &Digraph(vec![vec![], vec![], vec![]])
+        ));
+    }
+
+    #[test]
+    fn test_event_times() {
+        let graph = Digraph(vec![vec![1], vec![], vec![]]);
+        let mut events = dfs_events_from([0, 2], &graph);
+        let actual = events.by_ref().collect::<Vec<_>>();
+        let expected = vec![
+            DfsEvent::Discover { node: 0, time: 1 },
+            DfsEvent::Edge {
+                from: 0,
+                to: 1,
+                kind: EdgeKind::Tree,
+            },
+            DfsEvent::Discover { node: 1, time: 2 },
+            DfsEvent::Finish { node: 1, time: 3 },
+            DfsEvent::Finish { node: 0, time: 4 },
+            DfsEvent::Discover { node: 2, time: 5 },
+            DfsEvent::Finish { node: 2, time: 6 },
+        ];
+        assert_eq!(expected, actual);
+        assert_eq!(
+            (Some(2), Some(3)),
+            (events.discovery_time(&1), events.finish_time(&1))
+        );
+    }
+
+    #[test]
+    fn test_topological_order_respects_edges() {
+        let graph = Digraph(vec![
+            vec![],
+            vec![0],
+            vec![0, 1],
+            vec![2, 5],
+            vec![1],
+            vec![4],
+        ]);
+        let order = topological_order(0..6, &graph).unwrap();
+        let position = |n: usize| order.iter().position(|o| *o == n).unwrap();
+        assert_eq!(6, order.len());
+        assert!(
+            graph
+                .0
+                .iter()
+                .enumerate()
+                .all(|(from, tos)| tos.iter().all(|to| position(from) < position(*to)))
+        );
+    }
+}